@@ -0,0 +1,376 @@
+//! Why authenticating a user failed.
+//!
+//! Servers tell the reason with the result code of the bind.
+//! Active Directory always uses `invalidCredentials (49)`, but adds a subcode to the
+//! diagnostic message, e.g. `80090308: LdapErr: DSID-0C09044E, comment: AcceptSecurityContext error, data 775, v4563`.
+//! The subcodes are listed in the [ldapwiki](https://ldapwiki.com/wiki/Wiki.jsp?page=Common%20Active%20Directory%20Bind%20Errors).
+//! Servers supporting the Password Policy control tell the reason with it.
+//! OpenLDAP and 389-DS refuse locked and disabled accounts with `unwillingToPerform (53)`
+//! or `constraintViolation (19)`, telling the details only in the diagnostic message.
+
+use std::{error, fmt};
+
+use ldap3::{LdapError, LdapResult};
+
+use crate::{
+    ResultCode,
+    ppolicy::{PasswordPolicy, PasswordPolicyError},
+};
+
+/// Why the user couldn't be authenticated.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum AuthenticationFailureReason {
+    /// Wrong password, or no such user. Most servers don't tell these apart.
+    InvalidCredentials,
+    /// No such user. Only Active Directory tells this apart from a wrong password.
+    ///
+    /// Consider showing this to the end user as `InvalidCredentials`,
+    /// so that they can't probe for existing usernames.
+    UserNotFound,
+    /// The user isn't allowed to log in at this time of the day.
+    InvalidLogonHours,
+    /// The user isn't allowed to log in from this workstation.
+    InvalidWorkstation,
+    /// The password has expired.
+    PasswordExpired,
+    /// The account has been disabled.
+    AccountDisabled,
+    /// The account has expired.
+    AccountExpired,
+    /// The password has to be changed before logging in, e.g. after an administrative reset.
+    PasswordMustChange,
+    /// The account is locked, e.g. after too many failed attempts.
+    AccountLocked,
+    /// The credentials were rejected without asking the server, e.g. an empty password.
+    InvalidInput,
+    /// Some other reason. Check the result code and the diagnostic message of the error.
+    Other,
+}
+
+impl AuthenticationFailureReason {
+    /// Deduce the reason from the bind result.
+    fn from_result(result: &LdapResult, password_policy: &PasswordPolicy) -> Self {
+        match password_policy.error {
+            Some(PasswordPolicyError::PasswordExpired) => return Self::PasswordExpired,
+            Some(PasswordPolicyError::AccountLocked) => return Self::AccountLocked,
+            Some(PasswordPolicyError::ChangeAfterReset) => return Self::PasswordMustChange,
+            // The rest are about modifying passwords.
+            _ => (),
+        }
+
+        match ResultCode::from(result.rc) {
+            ResultCode::InvalidCredentials => active_directory_subcode(&result.text)
+                .and_then(Self::from_active_directory_subcode)
+                .unwrap_or(Self::InvalidCredentials),
+            // E.g. 389-DS "Account inactivated. Contact system administrator."
+            ResultCode::UnwillingToPerform => {
+                Self::from_diagnostic_message(&result.text).unwrap_or(Self::AccountDisabled)
+            }
+            // E.g. 389-DS "Exceed password retry limit. Please try later."
+            ResultCode::ConstraintViolation => {
+                Self::from_diagnostic_message(&result.text).unwrap_or(Self::AccountLocked)
+            }
+            _ => Self::Other,
+        }
+    }
+
+    /// Guess the reason from the wording of the diagnostic message.
+    fn from_diagnostic_message(message: &str) -> Option<Self> {
+        let message = message.to_ascii_lowercase();
+        let mentions = |words: &[&str]| words.iter().any(|word| message.contains(word));
+
+        let reason = if mentions(&["lock", "retry limit"]) {
+            Self::AccountLocked
+        } else if mentions(&["must be changed", "must change"]) {
+            Self::PasswordMustChange
+        } else if mentions(&["password"]) && mentions(&["expire"]) {
+            Self::PasswordExpired
+        } else if mentions(&["account"]) && mentions(&["expire"]) {
+            Self::AccountExpired
+        } else if mentions(&["inactivated", "inactive", "disabled"]) {
+            Self::AccountDisabled
+        } else {
+            return None;
+        };
+
+        Some(reason)
+    }
+
+    fn from_active_directory_subcode(subcode: &str) -> Option<Self> {
+        let reason = match subcode.to_ascii_lowercase().as_str() {
+            "52e" => Self::InvalidCredentials,
+            "525" => Self::UserNotFound,
+            "530" => Self::InvalidLogonHours,
+            "531" => Self::InvalidWorkstation,
+            "532" => Self::PasswordExpired,
+            "533" => Self::AccountDisabled,
+            "701" => Self::AccountExpired,
+            "773" => Self::PasswordMustChange,
+            "775" => Self::AccountLocked,
+            _ => return None,
+        };
+
+        Some(reason)
+    }
+}
+
+impl fmt::Display for AuthenticationFailureReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let description = match self {
+            Self::InvalidCredentials => "invalid credentials",
+            Self::UserNotFound => "user not found",
+            Self::InvalidLogonHours => "not allowed to log in at this time",
+            Self::InvalidWorkstation => "not allowed to log in from this workstation",
+            Self::PasswordExpired => "password expired",
+            Self::AccountDisabled => "account disabled",
+            Self::AccountExpired => "account expired",
+            Self::PasswordMustChange => "password must be changed",
+            Self::AccountLocked => "account locked",
+            Self::InvalidInput => "invalid input",
+            Self::Other => "other reason",
+        };
+
+        f.write_str(description)
+    }
+}
+
+/// Details of a failed authentication.
+///
+/// Carried by [`Error::AuthenticationFailed`](crate::Error::AuthenticationFailed).
+/// The result code and the diagnostic message are available through the error's inspection methods.
+/// If the bind didn't get as far as the server answering, e.g. the connection dropped,
+/// the underlying error is the [`source`](error::Error::source) of this.
+#[derive(Debug)]
+pub struct AuthenticationFailure {
+    reason: AuthenticationFailureReason,
+    message: String,
+    result: Option<LdapResult>,
+    source: Option<LdapError>,
+    password_policy: PasswordPolicy,
+}
+
+impl AuthenticationFailure {
+    /// A failure not coming from the server.
+    pub(crate) fn new(reason: AuthenticationFailureReason, message: String) -> Self {
+        Self {
+            reason,
+            message,
+            result: None,
+            source: None,
+            password_policy: PasswordPolicy::default(),
+        }
+    }
+
+    /// A failure to get an answer to the bind at all.
+    pub(crate) fn from_error(error: LdapError, user: &str) -> Self {
+        Self {
+            reason: AuthenticationFailureReason::Other,
+            message: format!("Error authenticating user {user:?}: {error}"),
+            result: None,
+            source: Some(error),
+            password_policy: PasswordPolicy::default(),
+        }
+    }
+
+    /// A failure as told by the result of the bind.
+    ///
+    /// The bind might have succeeded, but the password policy says otherwise.
+    pub(crate) fn from_result(
+        result: LdapResult,
+        password_policy: PasswordPolicy,
+        user: &str,
+    ) -> Self {
+        let reason = AuthenticationFailureReason::from_result(&result, &password_policy);
+        Self {
+            reason,
+            message: format!("Error authenticating user {user:?}: {reason}"),
+            result: Some(result),
+            source: None,
+            password_policy,
+        }
+    }
+
+    /// Why the authentication failed.
+    pub fn reason(&self) -> AuthenticationFailureReason {
+        self.reason
+    }
+
+    /// What the server told with the Password Policy control, if it supports it.
+    pub fn password_policy(&self) -> PasswordPolicy {
+        self.password_policy
+    }
+
+    pub(crate) fn ldap_result(&self) -> Option<&LdapResult> {
+        self.result.as_ref()
+    }
+
+    pub(crate) fn ldap_error(&self) -> Option<&LdapError> {
+        self.source.as_ref()
+    }
+}
+
+impl error::Error for AuthenticationFailure {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        self.source.as_ref().map(|source| source as _)
+    }
+}
+
+impl fmt::Display for AuthenticationFailure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+/// Find the hex subcode from an Active Directory diagnostic message.
+fn active_directory_subcode(message: &str) -> Option<&str> {
+    let (_, rest) = message.split_once("data ")?;
+    let end = rest
+        .find(|c: char| !c.is_ascii_hexdigit())
+        .unwrap_or(rest.len());
+
+    Some(&rest[..end]).filter(|subcode| !subcode.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bind_result(rc: u32, text: &str) -> LdapResult {
+        LdapResult {
+            rc,
+            matched: String::new(),
+            text: text.to_owned(),
+            refs: Vec::new(),
+            ctrls: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn active_directory_reasons() {
+        for (subcode, reason) in [
+            ("52e", AuthenticationFailureReason::InvalidCredentials),
+            ("525", AuthenticationFailureReason::UserNotFound),
+            ("530", AuthenticationFailureReason::InvalidLogonHours),
+            ("531", AuthenticationFailureReason::InvalidWorkstation),
+            ("532", AuthenticationFailureReason::PasswordExpired),
+            ("533", AuthenticationFailureReason::AccountDisabled),
+            ("701", AuthenticationFailureReason::AccountExpired),
+            ("773", AuthenticationFailureReason::PasswordMustChange),
+            ("775", AuthenticationFailureReason::AccountLocked),
+        ] {
+            let text = format!(
+                "80090308: LdapErr: DSID-0C09044E, comment: AcceptSecurityContext error, data {subcode}, v4563"
+            );
+            let failure = AuthenticationFailure::from_result(
+                bind_result(49, &text),
+                PasswordPolicy::default(),
+                "sam",
+            );
+            assert_eq!(failure.reason(), reason, "{subcode}");
+        }
+    }
+
+    #[test]
+    fn other_servers() {
+        let failure = AuthenticationFailure::from_result(
+            bind_result(49, ""),
+            PasswordPolicy::default(),
+            "sam",
+        );
+        assert_eq!(
+            failure.reason(),
+            AuthenticationFailureReason::InvalidCredentials
+        );
+        assert_eq!(
+            failure.to_string(),
+            "Error authenticating user \"sam\": invalid credentials"
+        );
+
+        let failure = AuthenticationFailure::from_result(
+            bind_result(80, "Nope"),
+            PasswordPolicy::default(),
+            "sam",
+        );
+        assert_eq!(failure.reason(), AuthenticationFailureReason::Other);
+    }
+
+    #[test]
+    fn refused_accounts() {
+        for (rc, text, reason) in [
+            (
+                53,
+                "Account inactivated. Contact system administrator.",
+                AuthenticationFailureReason::AccountDisabled,
+            ),
+            (53, "", AuthenticationFailureReason::AccountDisabled),
+            (
+                53,
+                "Account is locked",
+                AuthenticationFailureReason::AccountLocked,
+            ),
+            (
+                19,
+                "Exceed password retry limit. Please try later.",
+                AuthenticationFailureReason::AccountLocked,
+            ),
+            (
+                19,
+                "password expired!",
+                AuthenticationFailureReason::PasswordExpired,
+            ),
+            (19, "", AuthenticationFailureReason::AccountLocked),
+        ] {
+            let failure = AuthenticationFailure::from_result(
+                bind_result(rc, text),
+                PasswordPolicy::default(),
+                "sam",
+            );
+            assert_eq!(failure.reason(), reason, "{rc} {text}");
+        }
+    }
+
+    #[test]
+    fn keeps_the_source() {
+        let failure = AuthenticationFailure::from_error(LdapError::EndOfStream, "sam");
+        assert_eq!(failure.reason(), AuthenticationFailureReason::Other);
+        assert!(matches!(failure.ldap_error(), Some(LdapError::EndOfStream)));
+        assert!(error::Error::source(&failure).is_some());
+    }
+
+    #[test]
+    fn password_policy_reasons() {
+        // E.g. OpenLDAP locks accounts like this.
+        let password_policy = PasswordPolicy {
+            error: Some(PasswordPolicyError::AccountLocked),
+            ..PasswordPolicy::default()
+        };
+        let failure =
+            AuthenticationFailure::from_result(bind_result(49, ""), password_policy, "sam");
+        assert_eq!(failure.reason(), AuthenticationFailureReason::AccountLocked);
+        assert_eq!(failure.password_policy(), password_policy);
+
+        // The bind itself succeeds.
+        let password_policy = PasswordPolicy {
+            error: Some(PasswordPolicyError::ChangeAfterReset),
+            ..PasswordPolicy::default()
+        };
+        let failure =
+            AuthenticationFailure::from_result(bind_result(0, ""), password_policy, "sam");
+        assert_eq!(
+            failure.reason(),
+            AuthenticationFailureReason::PasswordMustChange
+        );
+    }
+
+    #[test]
+    fn find_subcode() {
+        assert_eq!(
+            active_directory_subcode("comment: AcceptSecurityContext error, data 52e, v2580"),
+            Some("52e")
+        );
+        assert_eq!(active_directory_subcode("data 775"), Some("775"));
+        assert_eq!(active_directory_subcode("No data here"), None);
+        assert_eq!(active_directory_subcode("Invalid credentials"), None);
+    }
+}
@@ -11,8 +11,13 @@
 //! - All the usual LDAP operations
 //! - Search result [deserialization](#deserialization)
 //! - Connection pooling
+//! - Opt-in reconnecting and retrying after connection failures
 //! - Streaming search with native rust [`Stream`](https://docs.rs/futures/latest/futures/stream/trait.Stream.html)s
+//! - Resumable paged search
 //! - Server Side Sort
+//! - Virtual List View
+//! - Search size and time limits
+//! - Referral chasing
 //!
 //!
 //! ## Usage
@@ -35,7 +40,7 @@
 //!
 //! ```no_run
 //! use simple_ldap::{
-//!     LdapClient, LdapConfig, SimpleDN,
+//!     BindMethod, LdapClient, LdapConfig, SimpleDN, TlsMode,
 //!     filter::EqFilter,
 //!     ldap3::Scope
 //! };
@@ -56,10 +61,16 @@
 //! #[tokio::main]
 //! async fn main(){
 //!     let ldap_config = LdapConfig {
-//!         bind_dn: String::from("cn=manager"),
-//!         bind_password: String::from("password"),
+//!         bind_dn: Some(String::from("cn=manager")),
+//!         bind_password: Some(String::from("password")),
 //!         ldap_url: Url::parse("ldaps://localhost:1389/dc=example,dc=com").unwrap(),
+//!         tls: TlsMode::Ldaps,
+//!         tls_options: None,
+//!         bind_method: BindMethod::Simple,
 //!         dn_attribute: None,
+//!         retry: None,
+//!         operation_timeout: None,
+//!         password_policy: false,
 //!         connection_settings: None
 //!     };
 //!     let mut client = LdapClient::new(ldap_config).await.unwrap();
@@ -137,35 +148,68 @@
 //! * `pool` - Enable connection pooling
 //!
 
-use futures::{Stream, StreamExt};
+use futures::{Stream, StreamExt, TryStreamExt, future::Either};
+use itertools::Itertools;
 use ldap3::{
-    Ldap, LdapConnAsync, LdapConnSettings, LdapError, Mod, Scope, SearchEntry,
+    Ldap, LdapConnAsync, LdapConnSettings, LdapError, LdapResult, Mod, ResultEntry, Scope,
+    SearchEntry, SearchResult,
     adapters::{Adapter, EntriesOnly, PagedResults},
+    asn1::{TagClass, Types, parse_tag},
+    controls::{self, Control, ControlType, RawControl},
+    exop::{Exop, PasswordModify, WhoAmI},
 };
 use serde::{Deserialize, Serialize};
 use serde_value::Value;
 use std::{
+    cmp::Reverse,
     collections::{HashMap, HashSet},
     fmt, iter,
-    num::NonZeroU16,
+    num::{NonZeroU16, NonZeroUsize},
+    sync::Arc,
+    time::Duration,
 };
 use thiserror::Error;
+use tokio::time::error::Elapsed;
 use tracing::{Level, debug, error, instrument, warn};
 use url::Url;
 
 use filter::{AndFilter, EqFilter, Filter, OrFilter};
+use ppolicy::control::PasswordPolicyRequest;
+use referral::ReferralChase;
+use retry::{Backoff, retry};
 use sort::adapter::ServerSideSort;
+use vlv::adapter::{VirtualListView, VirtualListViewResult};
 
+mod authentication;
 pub mod filter;
 #[cfg(feature = "pool")]
 pub mod pool;
+mod ppolicy;
+mod referral;
+mod result_code;
+mod retry;
+mod search_options;
 pub mod simple_dn;
 mod sort;
 mod stream;
+mod tls;
+mod vlv;
 // Export the main type of the module right here in the root.
 pub use simple_dn::SimpleDN;
+use simple_dn::SimpleDnParseError;
+// Returned by the error inspection methods.
+pub use authentication::{AuthenticationFailure, AuthenticationFailureReason};
+pub use ppolicy::{PasswordPolicy, PasswordPolicyError};
+pub use referral::ReferralPolicy;
+pub use result_code::ResultCode;
+pub use retry::RetryPolicy;
+pub use search_options::{SearchLimit, SearchOptions};
 // Used as an argument in the public API.
 pub use sort::adapter::SortBy;
+pub use tls::{
+    CertificateFingerprint, ClientCertificate, FingerprintParseError, PemSource, TlsOptions,
+};
+pub use vlv::{ListView, ViewTarget, Window};
 
 use crate::stream::to_native_stream;
 
@@ -175,6 +219,10 @@ pub extern crate ldap3;
 
 const LDAP_ENTRY_DN: &str = "entryDN";
 const NO_SUCH_RECORD: u32 = 32;
+/// <https://ldapwiki.com/wiki/Wiki.jsp?page=Tree%20Delete%20Control>
+const TREE_DELETE_OID: &str = "1.2.840.113556.1.4.805";
+// Big enough to keep the round trips down, small enough to stay under typical server limits.
+const SUBTREE_LISTING_PAGE_SIZE: NonZeroU16 = NonZeroU16::new(500).unwrap();
 
 /// Possible choices for the `objectClass` attribute of group entries.
 ///
@@ -203,22 +251,221 @@ impl Default for GroupObjectClass {
     }
 }
 
+/// Options for [`LdapClient::delete_subtree`].
+#[derive(Debug, Clone)]
+pub struct DeleteSubtreeOptions {
+    /// How many deletes to have in flight at once.
+    /// Only affects the client side deletion, used when the server doesn't support the Tree Delete control.
+    pub concurrency: NonZeroUsize,
+    /// Don't delete anything, just list what would be deleted.
+    pub dry_run: bool,
+}
+
+impl Default for DeleteSubtreeOptions {
+    fn default() -> Self {
+        Self {
+            concurrency: NonZeroUsize::new(4).unwrap(),
+            dry_run: false,
+        }
+    }
+}
+
+/// What [`LdapClient::search_all`] and [`LdapClient::get_members`] do with entries that can't be deserialized.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+pub enum MappingErrors {
+    /// Give up on the whole search at the first error.
+    #[default]
+    Fail,
+    /// Skip the failing entries, and return their errors alongside the rest.
+    Collect,
+}
+
+/// The outcome of the searches collecting all the results at once,
+/// like [`LdapClient::search_all`] and [`LdapClient::get_members`].
+#[derive(Debug)]
+pub struct Entries<T> {
+    /// The successfully deserialized entries, in the order the server returned them.
+    pub entries: Vec<T>,
+    /// Errors for the entries that were skipped.
+    ///
+    /// Always empty with [`MappingErrors::Fail`].
+    pub errors: Vec<Error>,
+    /// The limit that cut the search short, if any.
+    ///
+    /// The search is then a partial success: `entries` are the ones the server returned before the limit.
+    pub limit: Option<SearchLimit>,
+}
+
+/// A page of records returned by [`LdapClient::search_page`].
+#[derive(Debug)]
+pub struct Page<T> {
+    /// The deserialized records of the page, in the order the server returned them.
+    pub items: Vec<T>,
+    /// The cookie for fetching the next page, or `None` if this was the last one.
+    ///
+    /// It's opaque, only meaningful to the server that returned it, on the same connection.
+    pub next_cookie: Option<Vec<u8>>,
+}
+
 /// Configuration and authentication for LDAP connection
 #[derive(derive_more::Debug, Clone)]
 pub struct LdapConfig {
     pub ldap_url: Url,
+    /// How to secure the connection.
+    ///
+    /// Has to agree with the scheme of `ldap_url`.
+    pub tls: TlsMode,
+    /// Trust and identity settings for TLS.
+    ///
+    /// `None` trusts the system's certificate store.
+    /// Only allowed when `tls` isn't `TlsMode::None`.
+    pub tls_options: Option<TlsOptions>,
+    /// How to authenticate the connection.
+    pub bind_method: BindMethod,
     /// DistinguishedName, aka the "username" to use for the connection.
+    ///
+    /// Required with `BindMethod::Simple`, must be `None` otherwise.
     // Perhaps we don't want to use SimpleDN here, as it would make it impossible to bind to weird DNs.
-    pub bind_dn: String,
+    pub bind_dn: Option<String>,
+    /// Required with `BindMethod::Simple`, must be `None` otherwise.
     #[debug(skip)] // We don't want to print passwords.
-    pub bind_password: String,
+    pub bind_password: Option<String>,
     pub dn_attribute: Option<String>,
+    /// Reconnect and retry when the connection fails.
+    ///
+    /// `None` disables retrying, failing the operation right away.
+    /// See [`RetryPolicy`] for what gets retried.
+    pub retry: Option<RetryPolicy>,
+    /// Default timeout for the operations of the client.
+    ///
+    /// A timed out operation is abandoned on the server and fails with `Error::Timeout`.
+    /// Searches time out if the server doesn't send the next entry in time.
+    /// `None` waits indefinitely. Override it with [`LdapClient::with_timeout`].
+    pub operation_timeout: Option<Duration>,
+    /// Send the Password Policy control with simple binds of the client's own connection,
+    /// so that [`LdapClient::password_policy`] tells if its password is about to expire.
+    ///
+    /// `false` sends the bind without controls, like before. Authenticating users always asks for the policy.
+    pub password_policy: bool,
     /// Low level configuration for the connection.
     /// You can probably skip it.
     #[debug(skip)] // Debug omitted, because it just doesn't implement it.
     pub connection_settings: Option<LdapConnSettings>,
 }
 
+/// A successfully authenticated user.
+///
+/// Returned by [`LdapClient::authenticate_and_fetch`].
+#[derive(Debug, Clone)]
+pub struct Authenticated<T> {
+    /// DN of the user's entry.
+    pub dn: SimpleDN,
+    /// The user's entry.
+    pub entry: T,
+    /// What the server told about the user's password, e.g. that it's about to expire.
+    pub password_policy: PasswordPolicy,
+}
+
+/// How to turn a username into the name to bind as, without searching.
+///
+/// Used with [`LdapClient::authenticate_direct`].
+/// The templates contain a `{user}` placeholder for the username.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BindTemplate {
+    /// A DN, e.g. `uid={user},ou=people,dc=example,dc=com`.
+    ///
+    /// The username is escaped as an attribute value, so it can't change the structure of the DN.
+    Dn(String),
+    /// An Active Directory User Principal Name, e.g. `{user}@corp.example`.
+    ///
+    /// Usernames containing `@`, `\` or control characters are rejected,
+    /// so they can't pick another domain or use the down-level logon name format.
+    Upn(String),
+}
+
+impl BindTemplate {
+    const PLACEHOLDER: &str = "{user}";
+
+    /// The name to bind as for the given user.
+    fn render(&self, username: &str) -> Result<String, Error> {
+        let template = match self {
+            Self::Dn(template) | Self::Upn(template) => template,
+        };
+        if !template.contains(Self::PLACEHOLDER) {
+            return Err(Error::Config(format!(
+                "Bind template {template:?} doesn't contain {}",
+                Self::PLACEHOLDER
+            )));
+        }
+
+        let invalid_username = || {
+            Error::AuthenticationFailed(Box::new(AuthenticationFailure::new(
+                AuthenticationFailureReason::InvalidInput,
+                format!("Invalid username {username:?}"),
+            )))
+        };
+        if username.is_empty() {
+            return Err(invalid_username());
+        }
+
+        let value = match self {
+            Self::Dn(_) => ldap3::dn_escape(username),
+            Self::Upn(_)
+                if username
+                    .chars()
+                    .any(|c| c == '@' || c == '\\' || c.is_control()) =>
+            {
+                return Err(invalid_username());
+            }
+            Self::Upn(_) => username.into(),
+        };
+
+        Ok(template.replace(Self::PLACEHOLDER, &value))
+    }
+}
+
+/// How [`LdapClient::new`] authenticates the connection.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum BindMethod {
+    /// Simple bind with [`LdapConfig::bind_dn`] and [`LdapConfig::bind_password`].
+    ///
+    /// Both are required and the password can't be empty. RFC 4513 calls a simple bind
+    /// with a DN but no password an "unauthenticated bind", and many servers
+    /// let it succeed without checking anything.
+    Simple,
+    /// Don't bind at all. The server treats the connection as anonymous.
+    ///
+    /// Good for public read-only directories and probing the root DSE.
+    Anonymous,
+    /// SASL EXTERNAL bind.
+    ///
+    /// The identity comes from outside LDAP: a TLS client certificate
+    /// (see [`TlsOptions::client_certificate`]) or the peer credentials of an `ldapi://` socket.
+    /// The server decides what it maps to. Use [`LdapClient::who_am_i`] to find out.
+    SaslExternal,
+}
+
+/// Transport security of the LDAP connection.
+///
+/// The scheme of [`LdapConfig::ldap_url`] has to agree with this:
+/// `ldaps://` for `Ldaps` and `ldap://` for the others.
+/// (`None` also works with `ldapi://`.)
+/// Conflicting settings are rejected instead of guessing which one was meant.
+///
+/// `Ldaps` and `StartTls` need either the `tls-native` or the `tls-rustls` feature.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum TlsMode {
+    /// Plain text connection. Credentials are sent unencrypted, so only use this on trusted networks.
+    None,
+    /// TLS from the start, usually on port 636.
+    Ldaps,
+    /// Upgrade a plain connection to TLS with the StartTLS extended operation, usually on port 389.
+    ///
+    /// If the upgrade fails, the connection fails. Nothing, including the bind, is ever sent unencrypted.
+    StartTls,
+}
+
 ///
 /// High-level LDAP client wrapper on top of ldap3 crate. This wrapper provides a high-level interface to perform LDAP operations
 /// including authentication, search, update, delete
@@ -228,6 +475,53 @@ pub struct LdapClient {
     /// The internal connection handle.
     ldap: Ldap,
     dn_attr: Option<String>,
+    /// Who the connection is bound as.
+    ///
+    /// Doesn't change after creation, the client never rebinds its connection.
+    bound_identity: BindIdentity,
+    /// Told by the server on the bind made on creation.
+    password_policy: PasswordPolicy,
+    /// Starts from the config, but `with_timeout` changes it.
+    operation_timeout: Option<Duration>,
+    /// Set with `with_search_options`.
+    search_options: SearchOptions,
+    /// Needed for opening further connections, e.g. in `authenticate` and when reconnecting.
+    config: Arc<LdapConfig>,
+}
+
+/// The identity an LDAP connection is bound as.
+///
+/// This is what the client believes based on the bind it has made.
+/// Use [`LdapClient::who_am_i`] to ask the server.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BindIdentity {
+    /// Not bound, or bound without credentials.
+    Anonymous,
+    /// Bound with a simple bind as this DN.
+    Dn(String),
+    /// Bound with SASL EXTERNAL. The server knows who we are, ask it with [`LdapClient::who_am_i`].
+    SaslExternal,
+}
+
+impl fmt::Display for BindIdentity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Anonymous => write!(f, "anonymous"),
+            Self::Dn(dn) => write!(f, "{dn}"),
+            Self::SaslExternal => write!(f, "SASL EXTERNAL"),
+        }
+    }
+}
+
+impl BindIdentity {
+    /// The identity resulting from a successful simple bind.
+    fn simple(bind_dn: &str) -> Self {
+        if bind_dn.is_empty() {
+            Self::Anonymous
+        } else {
+            Self::Dn(bind_dn.to_owned())
+        }
+    }
 }
 
 impl LdapClient {
@@ -237,41 +531,139 @@ impl LdapClient {
     ///
     /// # Bind
     ///
-    /// This performs a simple bind on the connection so need to worry about that.
+    /// This binds the connection as configured in [`LdapConfig::bind_method`],
+    /// so no need to worry about that.
+    ///
+    /// # Reconnecting
+    ///
+    /// With [`LdapConfig::retry`] set, connection failures are retried here,
+    /// and in the idempotent operations (searches, compares, "Who am I?") later on.
+    /// A failed connection is replaced with a new one, bound the same way as the original.
+    ///
+    /// Clones of the client share the connection, but not the replacements.
+    /// Each clone reconnects on its own.
     ///
     pub async fn new(config: LdapConfig) -> Result<Self, Error> {
         debug!("Creating new connection");
 
-        // With or without connection settings
-        let (conn, mut ldap) = match config.connection_settings {
-            None => LdapConnAsync::from_url(&config.ldap_url).await,
-            Some(settings) => {
-                LdapConnAsync::from_url_with_settings(settings, &config.ldap_url).await
-            }
-        }
-        .map_err(|ldap_err| {
-            Error::Connection(
-                String::from("Failed to initialize LDAP connection."),
-                ldap_err,
-            )
-        })?;
-
-        ldap3::drive!(conn);
+        check_credentials(&config)?;
 
-        ldap.simple_bind(&config.bind_dn, &config.bind_password)
-            .await
-            .map_err(|ldap_err| Error::Connection(String::from("Bind failed"), ldap_err))?
-            .success()
-            .map_err(|ldap_err| Error::Connection(String::from("Bind failed"), ldap_err))?;
+        let (ldap, bound_identity, password_policy) =
+            retry(config.retry.as_ref(), || connect_and_bind(&config)).await?;
+        debug!("Connection bound as {bound_identity}");
 
         Ok(Self {
-            dn_attr: config.dn_attribute,
+            dn_attr: config.dn_attribute.clone(),
             ldap,
+            bound_identity,
+            password_policy,
+            operation_timeout: config.operation_timeout,
+            search_options: SearchOptions::default(),
+            config: Arc::new(config),
         })
     }
 }
 
 impl LdapClient {
+    /// A client with a different operation timeout than [`LdapConfig::operation_timeout`].
+    ///
+    /// The returned client shares the connection with this one, so this is cheap enough
+    /// to do for a single call.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use simple_ldap::{LdapClient, filter::EqFilter, ldap3::Scope};
+    /// use serde::Deserialize;
+    /// use std::time::Duration;
+    ///
+    /// #[derive(Debug, Deserialize)]
+    /// struct User {
+    ///     cn: String,
+    /// }
+    ///
+    /// async fn find(client: &LdapClient) {
+    ///     let filter = EqFilter::from("uid".to_string(), "sam".to_string());
+    ///
+    ///     let user = client
+    ///         .with_timeout(Duration::from_secs(2))
+    ///         .search::<_, _, _, User>("ou=people,dc=example,dc=com", Scope::OneLevel, &filter, vec!["cn"])
+    ///         .await;
+    /// }
+    /// ```
+    pub fn with_timeout(&self, timeout: Duration) -> Self {
+        Self {
+            operation_timeout: Some(timeout),
+            ..self.clone()
+        }
+    }
+
+    /// A client applying the options to its searches.
+    ///
+    /// This affects [`search`](Self::search), [`streaming_search`](Self::streaming_search),
+    /// [`get_members`](Self::get_members) and the like. Lookups that other operations
+    /// make internally, e.g. in [`authenticate`](Self::authenticate), aren't affected.
+    ///
+    /// The returned client shares the connection with this one, just like with [`with_timeout`](Self::with_timeout).
+    ///
+    /// # Limits
+    ///
+    /// A search cut short by a limit is a partial success. Streaming searches yield all the entries
+    /// the server returned, and then end with [`Error::LimitExceeded`] telling which limit was hit.
+    /// The searches collecting all the results, like [`search_all`](Self::search_all), return the entries
+    /// along with the limit in [`Entries::limit`].
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use simple_ldap::{Error, LdapClient, SearchOptions, filter::ContainsFilter, ldap3::Scope};
+    /// use futures::StreamExt;
+    /// use std::{collections::HashMap, num::NonZeroU32};
+    ///
+    /// async fn first_hundred(client: &LdapClient) {
+    ///     let mut client = client.with_search_options(SearchOptions {
+    ///         size_limit: NonZeroU32::new(100),
+    ///         ..SearchOptions::default()
+    ///     });
+    ///     let filter = ContainsFilter::from("cn".to_string(), "J".to_string());
+    ///
+    ///     let mut stream = Box::pin(client
+    ///         .streaming_search("dc=example,dc=com", Scope::Subtree, &filter, vec!["cn"], None, Vec::new())
+    ///         .await
+    ///         .unwrap());
+    ///
+    ///     while let Some(record) = stream.next().await {
+    ///         match record {
+    ///             Ok(record) => println!("{:?}", record.to_record::<HashMap<String, String>>()),
+    ///             Err(Error::LimitExceeded(_, limit)) => println!("There are more, cut by {limit:?}"),
+    ///             Err(error) => panic!("{error}"),
+    ///         }
+    ///     }
+    /// }
+    /// ```
+    pub fn with_search_options(&self, options: SearchOptions) -> Self {
+        Self {
+            search_options: options,
+            ..self.clone()
+        }
+    }
+
+    /// A handle for running a single operation, with the timeout applied.
+    fn operation_handle(&self) -> Ldap {
+        let mut ldap = self.ldap.clone();
+        if let Some(timeout) = self.operation_timeout {
+            ldap.with_timeout(timeout);
+        }
+        ldap
+    }
+
+    /// A handle for running a single search, with the timeout and the search options applied.
+    fn search_handle(&self) -> Ldap {
+        let mut ldap = self.operation_handle();
+        self.search_options.apply(&mut ldap);
+        ldap
+    }
+
     /// Returns the ldap3 client
     #[deprecated = "This abstraction leakage will be removed in a future release.
                     Use the provided methods instead. If something's missing, open an issue in github."]
@@ -295,7 +687,10 @@ impl LdapClient {
     pub async fn unbind(mut self) -> Result<(), Error> {
         match self.ldap.unbind().await {
             Ok(_) => Ok(()),
-            Err(error) => Err(Error::Close(String::from("Failed to unbind"), error)),
+            Err(error) => Err(Error::Close(
+                String::from("Failed to unbind"),
+                Box::new(error),
+            )),
         }
     }
 
@@ -303,6 +698,10 @@ impl LdapClient {
     /// The user is authenticated by searching for the user in the LDAP server.
     /// The search is performed using the provided filter. The filter should be a filter that matches a single user.
     ///
+    /// The password is checked by binding as the user on a separate short-lived connection,
+    /// opened with the same configuration as this client. This client's own connection
+    /// stays bound as before.
+    ///
     /// # Arguments
     ///
     /// * `base` - The base DN to search for the user
@@ -313,14 +712,15 @@ impl LdapClient {
     ///
     /// # Returns
     ///
-    /// * `Result<(), Error>` - Returns an error if the authentication fails
+    /// * `Result<PasswordPolicy, Error>` - Returns an error if the authentication fails.
+    ///   On success, whatever the server told with the Password Policy control.
     ///
     ///
     /// # Example
     ///
     /// ```no_run
     /// use simple_ldap::{
-    ///     LdapClient, LdapConfig,
+    ///     BindMethod, LdapClient, LdapConfig, TlsMode,
     ///     filter::EqFilter
     /// };
     /// use url::Url;
@@ -328,10 +728,16 @@ impl LdapClient {
     /// #[tokio::main]
     /// async fn main(){
     ///     let ldap_config = LdapConfig {
-    ///         bind_dn: String::from("cn=manager"),
-    ///         bind_password: String::from("password"),
+    ///         bind_dn: Some(String::from("cn=manager")),
+    ///         bind_password: Some(String::from("password")),
     ///         ldap_url: Url::parse("ldaps://localhost:1389/dc=example,dc=com").unwrap(),
+    ///         tls: TlsMode::Ldaps,
+    ///         tls_options: None,
+    ///         bind_method: BindMethod::Simple,
     ///         dn_attribute: None,
+    ///         retry: None,
+    ///         operation_timeout: None,
+    ///         password_policy: false,
     ///         connection_settings: None
     ///     };
     ///
@@ -347,18 +753,31 @@ impl LdapClient {
         uid: &str,
         password: &str,
         filter: Box<dyn Filter>,
-    ) -> Result<(), Error> {
-        let attr_dn = self.dn_attr.as_deref().unwrap_or(LDAP_ENTRY_DN);
-
-        let rs = self
-            .ldap
-            .search(base, Scope::OneLevel, filter.filter().as_str(), [attr_dn])
-            .await
-            .map_err(|e| Error::Query("Unable to query user for authentication".into(), e))?;
-
-        let (data, _rs) = rs
-            .success()
-            .map_err(|e| Error::Query("Could not find user for authentication".into(), e))?;
+    ) -> Result<PasswordPolicy, Error> {
+        // Owned so that `self` is free for retrying.
+        let attr_dn = self
+            .dn_attr
+            .clone()
+            .unwrap_or_else(|| LDAP_ENTRY_DN.to_owned());
+        let attr_dn = attr_dn.as_str();
+
+        let filter = &filter.filter();
+        let (data, _rs) = self
+            .retrying(|mut ldap| async move {
+                search_entries(&mut ldap, base, Scope::OneLevel, filter.as_str(), [attr_dn])
+                    .await?
+                    .map_err(|e| {
+                        Error::Query(
+                            "Unable to query user for authentication".into(),
+                            Box::new(e),
+                        )
+                    })?
+                    .success()
+                    .map_err(|e| {
+                        Error::Query("Could not find user for authentication".into(), Box::new(e))
+                    })
+            })
+            .await?;
 
         if data.is_empty() {
             return Err(Error::NotFound(format!("No record found {uid:?}")));
@@ -379,161 +798,496 @@ impl LdapClient {
             .collect();
 
         let entry_dn = result.get(attr_dn).ok_or_else(|| {
-            Error::AuthenticationFailed(format!("Unable to retrieve DN of user {uid}"))
+            Error::AuthenticationFailed(Box::new(AuthenticationFailure::new(
+                AuthenticationFailureReason::Other,
+                format!("Unable to retrieve DN of user {uid}"),
+            )))
         })?;
 
-        self.ldap
-            .simple_bind(entry_dn, password)
-            .await
-            .map_err(|_| Error::AuthenticationFailed(format!("Error authenticating user: {uid:?}")))
-            .and_then(|r| {
-                r.success().map_err(|_| {
-                    Error::AuthenticationFailed(format!("Error authenticating user: {uid:?}"))
-                })
-            })
-            .and(Ok(()))
+        self.bind_as_user(entry_dn, password, uid).await
     }
 
-    async fn search_inner<'a, F, A, S>(
-        &mut self,
-        base: &str,
-        scope: Scope,
-        filter: &F,
-        attributes: A,
-    ) -> Result<SearchEntry, Error>
-    where
-        F: Filter,
-        A: AsRef<[S]> + Send + Sync + 'a,
-        S: AsRef<str> + Send + Sync + 'a,
-    {
-        let search = self
-            .ldap
-            .search(base, scope, filter.filter().as_str(), attributes)
-            .await;
-        if let Err(error) = search {
-            return Err(Error::Query(
-                format!("Error searching for record: {error:?}"),
-                error,
-            ));
-        }
-        let result = search.unwrap().success();
-        if let Err(error) = result {
-            return Err(Error::Query(
-                format!("Error searching for record: {error:?}"),
-                error,
-            ));
-        }
-
-        let records = result.unwrap().0;
-
-        if records.len() > 1 {
-            return Err(Error::MultipleResults(String::from(
-                "Found multiple records for the search criteria",
-            )));
-        }
-
-        if records.is_empty() {
-            return Err(Error::NotFound(String::from(
-                "No records found for the search criteria",
-            )));
-        }
-
-        let record = records.first().unwrap();
-
-        Ok(SearchEntry::construct(record.to_owned()))
+    ///
+    /// Authenticate a user by binding directly with a name made from a template.
+    ///
+    /// Unlike [`authenticate`](Self::authenticate), this doesn't search for the user first.
+    /// So the client's own bind doesn't need to be able to read the users.
+    /// The bind happens on a separate short-lived connection, just like in `authenticate`.
+    ///
+    ///
+    /// # Arguments
+    ///
+    /// * `template` - How to make the bind name from the username
+    /// * `username` - The username as given by the user. It's escaped or validated as needed.
+    /// * `password` - The password of the user
+    ///
+    ///
+    /// # Returns
+    ///
+    /// * `Result<PasswordPolicy, Error>` - `Error::AuthenticationFailed` if the credentials are wrong
+    ///   or the username isn't valid. On success, whatever the server told with the Password Policy control.
+    ///
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use simple_ldap::{BindTemplate, LdapClient};
+    ///
+    /// async fn login(client: &LdapClient, username: &str, password: &str) {
+    ///     let template = BindTemplate::Dn(String::from("uid={user},ou=people,dc=example,dc=com"));
+    ///
+    ///     let result = client.authenticate_direct(&template, username, password).await;
+    /// }
+    /// ```
+    pub async fn authenticate_direct(
+        &self,
+        template: &BindTemplate,
+        username: &str,
+        password: &str,
+    ) -> Result<PasswordPolicy, Error> {
+        let bind_name = template.render(username)?;
+        self.bind_as_user(&bind_name, password, username).await
     }
 
     ///
-    /// Search a single value from the LDAP server. The search is performed using the provided filter.
-    /// The filter should be a filter that matches a single record. if the filter matches multiple users, an error is returned.
-    /// This operation will treat all the attributes as single-valued, silently ignoring the possible extra
-    /// values.
+    /// Authenticate a user and fetch their entry in one go.
+    ///
+    /// The user is looked up with the filter from the whole subtree under `base`,
+    /// and the password is checked like in [`authenticate`](Self::authenticate).
+    /// The same lookup provides the returned entry, so there's no need for a second search.
+    /// The [`SearchOptions`] of the client don't apply to the lookup.
     ///
     ///
     /// # Arguments
     ///
     /// * `base` - The base DN to search for the user
-    /// * `scope` - The scope of the search
-    /// * `filter` - The filter to search for the user
+    /// * `filter` - The filter to search for the user. Should match exactly one entry.
+    /// * `password` - The password of the user
     /// * `attributes` - The attributes to return from the search
     ///
     ///
     /// # Returns
     ///
-    /// * `Result<T, Error>` - The result will be mapped to a struct of type T
+    /// * `Result<Authenticated<T>, Error>` - The DN and the entry of the user mapped to T.
+    ///   `Error::AuthenticationFailed` if the password is wrong.
     ///
     ///
     /// # Example
     ///
     /// ```no_run
     /// use simple_ldap::{
-    ///     LdapClient, LdapConfig,
-    ///     filter::EqFilter,
-    ///     ldap3::Scope
+    ///     Authenticated, LdapClient,
+    ///     filter::EqFilter
     /// };
-    /// use url::Url;
     /// use serde::Deserialize;
     ///
-    ///
     /// #[derive(Debug, Deserialize)]
     /// struct User {
-    ///     uid: String,
     ///     cn: String,
-    ///     sn: String,
+    ///     mail: Option<String>,
     /// }
     ///
-    /// #[tokio::main]
-    /// async fn main(){
-    ///     let ldap_config = LdapConfig {
-    ///         bind_dn: String::from("cn=manager"),
-    ///         bind_password: String::from("password"),
-    ///         ldap_url: Url::parse("ldaps://localhost:1389/dc=example,dc=com").unwrap(),
-    ///         dn_attribute: None,
-    ///         connection_settings: None
-    ///     };
+    /// async fn login(client: &mut LdapClient, uid: &str, password: &str) {
+    ///     let filter = EqFilter::from("uid".to_string(), uid.to_string());
     ///
-    ///     let mut client = LdapClient::new(ldap_config).await.unwrap();
+    ///     let Authenticated { dn, entry, .. } = client
+    ///         .authenticate_and_fetch::<_, _, _, User>(
+    ///             "ou=people,dc=example,dc=com",
+    ///             &filter,
+    ///             password,
+    ///             vec!["cn", "mail"],
+    ///         )
+    ///         .await
+    ///         .unwrap();
     ///
-    ///     let name_filter = EqFilter::from("cn".to_string(), "Sam".to_string());
-    ///     let user_result: User = client
-    ///         .search(
-    ///         "ou=people,dc=example,dc=com",
-    ///         Scope::OneLevel,
-    ///         &name_filter,
-    ///         vec!["cn", "sn", "uid"],
-    ///     ).await
-    ///     .unwrap();
+    ///     println!("Welcome {}! ({dn})", entry.cn);
     /// }
     /// ```
-    ///
-    pub async fn search<'a, F, A, S, T>(
+    pub async fn authenticate_and_fetch<'a, F, A, S, T>(
         &mut self,
         base: &str,
-        scope: Scope,
         filter: &F,
+        password: &str,
         attributes: A,
-    ) -> Result<T, Error>
+    ) -> Result<Authenticated<T>, Error>
     where
         F: Filter,
         A: AsRef<[S]> + Send + Sync + 'a,
         S: AsRef<str> + Send + Sync + 'a,
         T: for<'de> serde::Deserialize<'de>,
     {
-        let search_entry = self.search_inner(base, scope, filter, attributes).await?;
-        to_value(search_entry)
+        // The options are for the searches of the application, not for finding the user.
+        let search_entry = self
+            .search_inner(
+                SearchOptions::default(),
+                base,
+                Scope::Subtree,
+                filter,
+                attributes,
+            )
+            .await?;
+
+        // The bind uses the DN as the server gave it, even if `SimpleDN` can't represent it.
+        let password_policy = self
+            .bind_as_user(&search_entry.dn, password, &search_entry.dn)
+            .await?;
+
+        let dn = parse_dn(&search_entry.dn)?;
+        let entry = to_value(search_entry)?;
+
+        Ok(Authenticated {
+            dn,
+            entry,
+            password_policy,
+        })
     }
 
+    /// Check the password of a user by binding as them.
     ///
-    /// Search a single value from the LDAP server. The search is performed using the provided filter.
-    /// The filter should be a filter that matches a single record. if the filter matches multiple users, an error is returned.
-    /// This operation is useful when records has multi-valued attributes.
-    ///
+    /// The bind asks for the password policy of the user. A policy error fails the
+    /// authentication even if the server accepted the bind.
     ///
-    /// # Arguments
+    /// `user` is only used for error messages.
+    async fn bind_as_user(
+        &self,
+        dn: &str,
+        password: &str,
+        user: &str,
+    ) -> Result<PasswordPolicy, Error> {
+        // A bind with an empty password would be an "unauthenticated bind",
+        // which many servers consider a success.
+        if password.is_empty() {
+            return Err(Error::AuthenticationFailed(Box::new(
+                AuthenticationFailure::new(
+                    AuthenticationFailureReason::InvalidInput,
+                    format!("Empty password for user {user:?}"),
+                ),
+            )));
+        }
+
+        // Binding on our own connection would change who it's bound as,
+        // for all the clones and for whoever gets it from a pool next.
+        // So the user gets a connection of their own.
+        let mut user_ldap = retry(self.config.retry.as_ref(), || connect(&self.config)).await?;
+
+        let bind_result = match user_ldap
+            .with_controls(PasswordPolicyRequest)
+            .simple_bind(dn, password)
+            .await
+        {
+            Ok(result) => {
+                let password_policy = ppolicy::password_policy(&result.ctrls);
+                if result.rc == 0 && password_policy.error.is_none() {
+                    Ok(password_policy)
+                } else {
+                    Err(Error::AuthenticationFailed(Box::new(
+                        AuthenticationFailure::from_result(result, password_policy, user),
+                    )))
+                }
+            }
+            Err(error) => Err(Error::AuthenticationFailed(Box::new(
+                AuthenticationFailure::from_error(error, user),
+            ))),
+        };
+
+        // Just a courtesy, the connection closes when dropped anyway.
+        if let Err(unbind_err) = user_ldap.unbind().await {
+            debug!("Unbinding the authentication connection failed: {unbind_err}");
+        }
+
+        bind_result
+    }
+
+    /// Run an idempotent operation, reconnecting and retrying as configured in [`LdapConfig::retry`].
     ///
-    /// * `base` - The base DN to search for the user
-    /// * `scope` - The scope of the search
-    /// * `filter` - The filter to search for the user
+    /// The operation gets a handle to the current connection on each attempt,
+    /// with the operation timeout applied.
+    async fn retrying<T, F, Fut>(&mut self, mut operation: F) -> Result<T, Error>
+    where
+        F: FnMut(Ldap) -> Fut,
+        Fut: Future<Output = Result<T, Error>>,
+    {
+        // The backoff borrows the config, while reconnecting needs `self` mutably.
+        let config = Arc::clone(&self.config);
+        let mut backoff = Backoff::new(config.retry.as_ref());
+        let mut result = operation(self.operation_handle()).await;
+
+        loop {
+            let error = match result {
+                Err(error) if backoff.wait(&error).await => error,
+                result => return result,
+            };
+
+            // A busy server is fine to ask again on the same connection.
+            result = if error.is_connection_error() || self.ldap.is_closed() {
+                match self.reconnect().await {
+                    Ok(()) => operation(self.operation_handle()).await,
+                    Err(reconnect_error) => Err(reconnect_error),
+                }
+            } else {
+                operation(self.operation_handle()).await
+            };
+        }
+    }
+
+    /// Run an operation that isn't safe to repeat, like a write.
+    ///
+    /// See [`recover`](Self::recover) for what happens if the connection is lost.
+    async fn reconnecting<T>(
+        &mut self,
+        operation: impl AsyncFnOnce(Ldap) -> Result<T, Error>,
+    ) -> Result<T, Error> {
+        let result = operation(self.operation_handle()).await;
+        self.recover(result).await
+    }
+
+    /// Replace the connection if the failed operation lost it, when retrying is configured.
+    ///
+    /// The operation itself isn't replayed, as it may have gone through before the connection was lost.
+    /// Its error is returned as is, and the next operations use the new connection.
+    async fn recover<T>(&mut self, result: Result<T, Error>) -> Result<T, Error> {
+        if let Err(error) = &result
+            && self.config.retry.is_some()
+            && (error.is_connection_error() || self.ldap.is_closed())
+        {
+            warn!("Reconnecting after a lost connection: {error}");
+            if let Err(reconnect_error) = self.reconnect().await {
+                // The next operations will try again.
+                warn!("Reconnecting failed: {reconnect_error}");
+            }
+        }
+
+        result
+    }
+
+    /// Replace the connection with a new one, bound like the original.
+    async fn reconnect(&mut self) -> Result<(), Error> {
+        debug!("Reconnecting");
+        let (ldap, bound_identity, password_policy) = connect_and_bind(&self.config).await?;
+        debug_assert_eq!(bound_identity, self.bound_identity);
+
+        self.ldap = ldap;
+        self.password_policy = password_policy;
+
+        Ok(())
+    }
+
+    /// The identity this client believes its connection to be bound as.
+    ///
+    /// This is set from the bind made on creation. Nothing else changes it,
+    /// [`authenticate`](Self::authenticate) included.
+    pub fn bound_identity(&self) -> BindIdentity {
+        self.bound_identity.clone()
+    }
+
+    /// What the server told about the password of the client's own bind.
+    ///
+    /// Simple binds send the Password Policy control if [`LdapConfig::password_policy`] is set,
+    /// otherwise this is always the default.
+    /// Keep an eye on this if the service account's password can expire.
+    pub fn password_policy(&self) -> PasswordPolicy {
+        self.password_policy
+    }
+
+    ///
+    /// Ask the server which identity the connection is bound as, using the "Who am I?" extended operation ([RFC 4532](https://datatracker.ietf.org/doc/html/rfc4532)).
+    ///
+    /// # Returns
+    ///
+    /// * `Result<Option<String>, Error>` - The authorization identity, e.g. "dn:cn=manager".
+    ///   `None` means that the connection is anonymous.
+    ///   [`Error::Extended`] if the identity isn't valid UTF-8.
+    ///
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use simple_ldap::{BindMethod, LdapClient, LdapConfig, TlsMode};
+    /// use url::Url;
+    ///
+    /// #[tokio::main]
+    /// async fn main(){
+    ///     let ldap_config = LdapConfig {
+    ///         bind_dn: Some(String::from("cn=manager")),
+    ///         bind_password: Some(String::from("password")),
+    ///         ldap_url: Url::parse("ldaps://localhost:1389/dc=example,dc=com").unwrap(),
+    ///         tls: TlsMode::Ldaps,
+    ///         tls_options: None,
+    ///         bind_method: BindMethod::Simple,
+    ///         dn_attribute: None,
+    ///         retry: None,
+    ///         operation_timeout: None,
+    ///         password_policy: false,
+    ///         connection_settings: None
+    ///     };
+    ///
+    ///     let mut client = LdapClient::new(ldap_config).await.unwrap();
+    ///
+    ///     let identity = client.who_am_i().await.unwrap();
+    ///     assert_eq!(identity.as_deref(), Some("dn:cn=manager"));
+    /// }
+    /// ```
+    pub async fn who_am_i(&mut self) -> Result<Option<String>, Error> {
+        let (exop, _) = self
+            .retrying(|mut ldap| async move {
+                let who_am_i = ldap.extended(WhoAmI).await;
+                check_timeout(&mut ldap, who_am_i)
+                    .await?
+                    .and_then(|res| res.success())
+                    .map_err(|err| {
+                        Error::Extended(format!("Error asking who am I: {err:?}"), Box::new(err))
+                    })
+            })
+            .await?;
+
+        parse_authzid(exop)
+    }
+
+    /// Search for exactly one entry, with the given options.
+    async fn search_inner<'a, F, A, S>(
+        &mut self,
+        options: SearchOptions,
+        base: &str,
+        scope: Scope,
+        filter: &F,
+        attributes: A,
+    ) -> Result<SearchEntry, Error>
+    where
+        F: Filter,
+        A: AsRef<[S]> + Send + Sync + 'a,
+        S: AsRef<str> + Send + Sync + 'a,
+    {
+        let filter = &filter.filter();
+        let attributes = attributes.as_ref();
+        let options = &options;
+        let (records, _) = self
+            .retrying(|mut ldap| async move {
+                options.apply(&mut ldap);
+                search_entries(&mut ldap, base, scope, filter.as_str(), attributes)
+                    .await?
+                    .and_then(|search| search.success())
+                    .map_err(|error| {
+                        search_error(format!("Error searching for record: {error:?}"), error)
+                    })
+            })
+            .await?;
+
+        if records.len() > 1 {
+            return Err(Error::MultipleResults(String::from(
+                "Found multiple records for the search criteria",
+            )));
+        }
+
+        if records.is_empty() {
+            return Err(Error::NotFound(String::from(
+                "No records found for the search criteria",
+            )));
+        }
+
+        let record = records.first().unwrap();
+
+        Ok(SearchEntry::construct(record.to_owned()))
+    }
+
+    ///
+    /// Search a single value from the LDAP server. The search is performed using the provided filter.
+    /// The filter should be a filter that matches a single record. if the filter matches multiple users, an error is returned.
+    /// This operation will treat all the attributes as single-valued, silently ignoring the possible extra
+    /// values.
+    ///
+    ///
+    /// # Arguments
+    ///
+    /// * `base` - The base DN to search for the user
+    /// * `scope` - The scope of the search
+    /// * `filter` - The filter to search for the user
+    /// * `attributes` - The attributes to return from the search
+    ///
+    ///
+    /// # Returns
+    ///
+    /// * `Result<T, Error>` - The result will be mapped to a struct of type T
+    ///
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use simple_ldap::{
+    ///     BindMethod, LdapClient, LdapConfig, TlsMode,
+    ///     filter::EqFilter,
+    ///     ldap3::Scope
+    /// };
+    /// use url::Url;
+    /// use serde::Deserialize;
+    ///
+    ///
+    /// #[derive(Debug, Deserialize)]
+    /// struct User {
+    ///     uid: String,
+    ///     cn: String,
+    ///     sn: String,
+    /// }
+    ///
+    /// #[tokio::main]
+    /// async fn main(){
+    ///     let ldap_config = LdapConfig {
+    ///         bind_dn: Some(String::from("cn=manager")),
+    ///         bind_password: Some(String::from("password")),
+    ///         ldap_url: Url::parse("ldaps://localhost:1389/dc=example,dc=com").unwrap(),
+    ///         tls: TlsMode::Ldaps,
+    ///         tls_options: None,
+    ///         bind_method: BindMethod::Simple,
+    ///         dn_attribute: None,
+    ///         retry: None,
+    ///         operation_timeout: None,
+    ///         password_policy: false,
+    ///         connection_settings: None
+    ///     };
+    ///
+    ///     let mut client = LdapClient::new(ldap_config).await.unwrap();
+    ///
+    ///     let name_filter = EqFilter::from("cn".to_string(), "Sam".to_string());
+    ///     let user_result: User = client
+    ///         .search(
+    ///         "ou=people,dc=example,dc=com",
+    ///         Scope::OneLevel,
+    ///         &name_filter,
+    ///         vec!["cn", "sn", "uid"],
+    ///     ).await
+    ///     .unwrap();
+    /// }
+    /// ```
+    ///
+    pub async fn search<'a, F, A, S, T>(
+        &mut self,
+        base: &str,
+        scope: Scope,
+        filter: &F,
+        attributes: A,
+    ) -> Result<T, Error>
+    where
+        F: Filter,
+        A: AsRef<[S]> + Send + Sync + 'a,
+        S: AsRef<str> + Send + Sync + 'a,
+        T: for<'de> serde::Deserialize<'de>,
+    {
+        let search_entry = self
+            .search_inner(self.search_options.clone(), base, scope, filter, attributes)
+            .await?;
+        to_value(search_entry)
+    }
+
+    ///
+    /// Search a single value from the LDAP server. The search is performed using the provided filter.
+    /// The filter should be a filter that matches a single record. if the filter matches multiple users, an error is returned.
+    /// This operation is useful when records has multi-valued attributes.
+    ///
+    ///
+    /// # Arguments
+    ///
+    /// * `base` - The base DN to search for the user
+    /// * `scope` - The scope of the search
+    /// * `filter` - The filter to search for the user
     /// * `attributes` - The attributes to return from the search
     ///
     ///
@@ -546,7 +1300,7 @@ impl LdapClient {
     ///
     /// ```no_run
     /// use simple_ldap::{
-    ///     LdapClient, LdapConfig,
+    ///     BindMethod, LdapClient, LdapConfig, TlsMode,
     ///     filter::EqFilter,
     ///     ldap3::Scope
     /// };
@@ -563,10 +1317,16 @@ impl LdapClient {
     /// #[tokio::main]
     /// async fn main(){
     ///     let ldap_config = LdapConfig {
-    ///         bind_dn: String::from("cn=manager"),
-    ///         bind_password: String::from("password"),
+    ///         bind_dn: Some(String::from("cn=manager")),
+    ///         bind_password: Some(String::from("password")),
     ///         ldap_url: Url::parse("ldaps://localhost:1389/dc=example,dc=com").unwrap(),
+    ///         tls: TlsMode::Ldaps,
+    ///         tls_options: None,
+    ///         bind_method: BindMethod::Simple,
     ///         dn_attribute: None,
+    ///         retry: None,
+    ///         operation_timeout: None,
+    ///         password_policy: false,
     ///         connection_settings: None
     ///     };
     ///
@@ -589,10 +1349,126 @@ impl LdapClient {
         filter: &impl Filter,
         attributes: &Vec<&str>,
     ) -> Result<T, Error> {
-        let search_entry = self.search_inner(base, scope, filter, attributes).await?;
+        let search_entry = self
+            .search_inner(self.search_options.clone(), base, scope, filter, attributes)
+            .await?;
         to_multi_value(search_entry)
     }
 
+    ///
+    /// Search a single record from the LDAP server, if there is one.
+    ///
+    /// Like [`search`](Self::search), but a record not being there isn't an error.
+    /// Neither no records matching the filter nor the base DN not existing
+    /// ("noSuchObject") count as a failure, both are `None`.
+    /// Multiple matching records are still an error.
+    ///
+    ///
+    /// # Arguments
+    ///
+    /// * `base` - The base DN to search for the record
+    /// * `scope` - The scope of the search
+    /// * `filter` - The filter to search for the record
+    /// * `attributes` - The attributes to return from the search
+    ///
+    ///
+    /// # Returns
+    ///
+    /// * `Result<Option<T>, Error>` - The record mapped to a struct of type T, or `None` if there was none.
+    ///
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use simple_ldap::{LdapClient, filter::EqFilter, ldap3::Scope};
+    /// use serde::Deserialize;
+    ///
+    /// #[derive(Debug, Deserialize)]
+    /// struct User {
+    ///     uid: String,
+    ///     cn: String,
+    /// }
+    ///
+    /// async fn find_sam(client: &mut LdapClient) -> Option<User> {
+    ///     let name_filter = EqFilter::from("cn".to_string(), "Sam".to_string());
+    ///     client
+    ///         .find_one("ou=people,dc=example,dc=com", Scope::OneLevel, &name_filter, vec!["cn", "uid"])
+    ///         .await
+    ///         .unwrap()
+    /// }
+    /// ```
+    ///
+    pub async fn find_one<'a, F, A, S, T>(
+        &mut self,
+        base: &str,
+        scope: Scope,
+        filter: &F,
+        attributes: A,
+    ) -> Result<Option<T>, Error>
+    where
+        F: Filter,
+        A: AsRef<[S]> + Send + Sync + 'a,
+        S: AsRef<str> + Send + Sync + 'a,
+        T: for<'de> serde::Deserialize<'de>,
+    {
+        match self
+            .search_inner(self.search_options.clone(), base, scope, filter, attributes)
+            .await
+        {
+            Ok(search_entry) => to_value(search_entry).map(Some),
+            // Either nothing matched, or the base itself doesn't exist.
+            Err(error) if error.result_code() == Some(ResultCode::NoSuchObject) => Ok(None),
+            Err(error) => Err(error),
+        }
+    }
+
+    ///
+    /// Read a single record by its DN.
+    ///
+    /// This is a base scope search, returning `None` if the record doesn't exist.
+    ///
+    ///
+    /// # Arguments
+    ///
+    /// * `dn` - The DN of the record
+    /// * `attributes` - The attributes to return
+    ///
+    ///
+    /// # Returns
+    ///
+    /// * `Result<Option<T>, Error>` - The record mapped to a struct of type T, or `None` if there is no such record.
+    ///
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use simple_ldap::LdapClient;
+    /// use serde::Deserialize;
+    ///
+    /// #[derive(Debug, Deserialize)]
+    /// struct User {
+    ///     uid: String,
+    ///     cn: String,
+    /// }
+    ///
+    /// async fn read_sam(client: &mut LdapClient) -> Option<User> {
+    ///     client
+    ///         .get("uid=sam,ou=people,dc=example,dc=com", vec!["cn", "uid"])
+    ///         .await
+    ///         .unwrap()
+    /// }
+    /// ```
+    ///
+    pub async fn get<'a, A, S, T>(&mut self, dn: &str, attributes: A) -> Result<Option<T>, Error>
+    where
+        A: AsRef<[S]> + Send + Sync + 'a,
+        S: AsRef<str> + Send + Sync + 'a,
+        T: for<'de> serde::Deserialize<'de>,
+    {
+        let any_entry = EqFilter::from(String::from("objectClass"), String::from("*"));
+        self.find_one(dn, Scope::Base, &any_entry, attributes).await
+    }
+
     ///
     /// This method is used to search multiple records from the LDAP server. The search is performed using the provided filter.
     /// Method will return a Stream. The stream will lazily fetch the results, resulting in a smaller
@@ -616,6 +1492,10 @@ impl LdapClient {
     //
     /// A stream that can be used to iterate through the search results.
     ///
+    /// If a size or time limit cuts the search short, the stream ends with [`Error::LimitExceeded`].
+    /// See [`with_search_options`](Self::with_search_options).
+    /// Referrals are handled as the [`ReferralPolicy`] in the search options says.
+    ///
     ///
     /// ## Blocking drop caveat
     ///
@@ -630,7 +1510,7 @@ impl LdapClient {
     ///
     /// ```no_run
     /// use simple_ldap::{
-    ///     LdapClient, LdapConfig, SortBy,
+    ///     BindMethod, LdapClient, LdapConfig, SortBy, TlsMode,
     ///     filter::EqFilter,
     ///     ldap3::Scope,
     /// };
@@ -650,365 +1530,1692 @@ impl LdapClient {
     /// #[tokio::main]
     /// async fn main(){
     ///     let ldap_config = LdapConfig {
-    ///         bind_dn: String::from("cn=manager"),
-    ///         bind_password: String::from("password"),
+    ///         bind_dn: Some(String::from("cn=manager")),
+    ///         bind_password: Some(String::from("password")),
+    ///         ldap_url: Url::parse("ldaps://localhost:1389/dc=example,dc=com").unwrap(),
+    ///         tls: TlsMode::Ldaps,
+    ///         tls_options: None,
+    ///         bind_method: BindMethod::Simple,
+    ///         dn_attribute: None,
+    ///         retry: None,
+    ///         operation_timeout: None,
+    ///         password_policy: false,
+    ///         connection_settings: None
+    ///     };
+    ///
+    ///     let mut client = LdapClient::new(ldap_config).await.unwrap();
+    ///
+    ///     let name_filter = EqFilter::from(String::from("cn"), String::from("Sam"));
+    ///     let attributes = vec!["cn", "sn", "uid"];
+    ///     let sort = vec![
+    ///         SortBy {
+    ///             attribute: String::from("sn"),
+    ///             reverse: true
+    ///         }
+    ///     ];
+    ///
+    ///     let stream = client.streaming_search(
+    ///         "ou=people,dc=example,dc=com",
+    ///         Scope::OneLevel,
+    ///         &name_filter,
+    ///         attributes,
+    ///         Some(NonZero::new(200).unwrap()), // The pagesize
+    ///         sort
+    ///     ).await.unwrap();
+    ///
+    ///     // Map the search results to User type.
+    ///     stream.and_then(async |record| record.to_record())
+    ///          // Do something with the Users concurrently.
+    ///         .try_for_each(async |user: User| {
+    ///             println!("User: {:?}", user);
+    ///             Ok(())
+    ///         })
+    ///         .await
+    ///         .unwrap();
+    /// }
+    /// ```
+    ///
+    pub async fn streaming_search<'a, F, A, S>(
+        // This self reference  lifetime has some nuance behind it.
+        //
+        // In principle it could just be a value, but then you wouldn't be able to call this
+        // with a pooled client, as the deadpool `Object` wrapper only ever gives out references.
+        //
+        // The lifetime is needed to guarantee that the client is not returned to the pool before
+        // the returned stream is finished. This requirement is artificial. Internally the `ldap3` client
+        // just makes copy. So this lifetime is here just to enforce correct pool usage.
+        &'a mut self,
+        base: &str,
+        scope: Scope,
+        filter: &F,
+        attributes: A,
+        // The internal adapter takes i32, but half of its range is invalid.
+        page_size: Option<NonZeroU16>,
+        sort_by: Vec<SortBy>,
+    ) -> Result<impl Stream<Item = Result<Record, Error>> + use<'a, F, A, S>, Error>
+    where
+        F: Filter,
+        // PagedResults requires Clone and Debug too.
+        A: AsRef<[S]> + Send + Sync + Clone + fmt::Debug + 'a,
+        S: AsRef<str> + Send + Sync + Clone + fmt::Debug + 'a,
+    {
+        let filter = filter.filter();
+        let referrals = self.search_options.referrals;
+        // Chasing needs its own copy of the search, for later.
+        let chase = match referrals {
+            ReferralPolicy::Chase {
+                hop_limit,
+                allow_plaintext,
+            } => Some((
+                ReferralChase {
+                    config: Arc::clone(&self.config),
+                    scope,
+                    filter: filter.clone(),
+                    attributes: attributes
+                        .as_ref()
+                        .iter()
+                        .map(|attribute| attribute.as_ref().to_owned())
+                        .collect(),
+                    page_size,
+                    sort_by: sort_by.clone(),
+                    search_options: self.search_options.clone(),
+                    operation_timeout: self.operation_timeout,
+                    allow_plaintext,
+                },
+                hop_limit,
+            )),
+            ReferralPolicy::Ignore | ReferralPolicy::Surface => None,
+        };
+
+        let options = &self.search_options.clone();
+        let (filter, attributes, sort_by) = (&filter, &attributes, &sort_by);
+        // Only starting the search is retried, the stream itself can't be resumed.
+        let search_stream = self
+            .retrying(|mut ldap: Ldap| async move {
+                options.apply(&mut ldap);
+                // Inference would make the adapter lifetime 'static.
+                let adapters = search_adapters::<'a, S, A>(page_size, sort_by.clone())?;
+                ldap.streaming_search_with(adapters, base, scope, filter, attributes.clone())
+                    .await
+                    .map_err(start_search_error)
+            })
+            .await?;
+        let records = to_native_stream(search_stream, referrals)?;
+
+        Ok(match chase {
+            Some((chase, hop_limit)) => {
+                Either::Left(Arc::new(chase).chase(records, hop_limit.get()))
+            }
+            None => Either::Right(records),
+        })
+    }
+
+    ///
+    /// Like [`streaming_search`](Self::streaming_search), but the stream yields the records
+    /// already mapped to a struct of type T.
+    ///
+    /// [`Error::Mapping`] errors carry the DN of the entry that failed. They don't end the stream,
+    /// so you can skip the failing entries if you want to.
+    ///
+    ///
+    /// # Arguments
+    ///
+    /// Same as with `streaming_search`.
+    ///
+    ///
+    /// # Returns
+    ///
+    /// A stream of the mapped records.
+    /// The blocking drop caveat of `streaming_search` applies to this too.
+    ///
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use simple_ldap::{LdapClient, SortBy, filter::EqFilter, ldap3::Scope};
+    /// use serde::Deserialize;
+    /// use futures::TryStreamExt;
+    /// use std::num::NonZero;
+    ///
+    /// #[derive(Deserialize, Debug)]
+    /// struct User {
+    ///     uid: String,
+    ///     cn: String,
+    ///     sn: String,
+    /// }
+    ///
+    /// async fn print_users(client: &mut LdapClient) {
+    ///     let name_filter = EqFilter::from(String::from("cn"), String::from("Sam"));
+    ///     let sort = vec![
+    ///         SortBy {
+    ///             attribute: String::from("sn"),
+    ///             reverse: true
+    ///         }
+    ///     ];
+    ///
+    ///     client
+    ///         .streaming_search_as(
+    ///             "ou=people,dc=example,dc=com",
+    ///             Scope::OneLevel,
+    ///             &name_filter,
+    ///             vec!["cn", "sn", "uid"],
+    ///             Some(NonZero::new(200).unwrap()),
+    ///             sort,
+    ///         )
+    ///         .await
+    ///         .unwrap()
+    ///         .try_for_each(async |user: User| {
+    ///             println!("User: {:?}", user);
+    ///             Ok(())
+    ///         })
+    ///         .await
+    ///         .unwrap();
+    /// }
+    /// ```
+    ///
+    pub async fn streaming_search_as<'a, F, A, S, T>(
+        // See `streaming_search` for the lifetime.
+        &'a mut self,
+        base: &str,
+        scope: Scope,
+        filter: &F,
+        attributes: A,
+        page_size: Option<NonZeroU16>,
+        sort_by: Vec<SortBy>,
+    ) -> Result<impl Stream<Item = Result<T, Error>> + use<'a, F, A, S, T>, Error>
+    where
+        F: Filter,
+        A: AsRef<[S]> + Send + Sync + Clone + fmt::Debug + 'a,
+        S: AsRef<str> + Send + Sync + Clone + fmt::Debug + 'a,
+        T: for<'de> serde::Deserialize<'de>,
+    {
+        let stream = self
+            .streaming_search(base, scope, filter, attributes, page_size, sort_by)
+            .await?;
+
+        Ok(stream.map(|record| record.and_then(Record::to_record)))
+    }
+
+    ///
+    /// Search multiple records from the LDAP server and collect them into a `Vec`.
+    ///
+    /// This is a convenience on top of [`streaming_search`](Self::streaming_search) for result
+    /// sets small enough to be held in memory at once. Unlike [`search`](Self::search),
+    /// any number of matching entries is fine, including none.
+    ///
+    ///
+    /// # Arguments
+    ///
+    /// * `base` - The base DN to search for the records
+    /// * `scope` - The scope of the search
+    /// * `filter` - The filter to search for the records
+    /// * `attributes` - The attributes to return from the search
+    /// * `page_size` - Fetch the results in pages, as in `streaming_search`.
+    /// * `sort_by` - Sort the results using Server Side Sort LDAP extension.
+    /// * `mapping_errors` - Whether to fail or to collect the errors of entries that can't be deserialized.
+    ///
+    ///
+    /// # Returns
+    ///
+    /// * `Result<Entries<T>, Error>` - The deserialized entries, and with [`MappingErrors::Collect`]
+    ///   the errors of the skipped ones.
+    ///   A size or time limit cutting the search short doesn't fail it, the entries before it are returned
+    ///   with [`Entries::limit`] telling which limit was hit.
+    ///   Referrals aren't mapping errors, they follow the [`ReferralPolicy`] of the client.
+    ///   With [`ReferralPolicy::Surface`] the first one fails the search with [`Error::Referral`].
+    ///
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use simple_ldap::{
+    ///     BindMethod, LdapClient, LdapConfig, MappingErrors, SortBy, TlsMode,
+    ///     filter::ContainsFilter,
+    ///     ldap3::Scope,
+    /// };
+    /// use url::Url;
+    /// use serde::Deserialize;
+    ///
+    ///
+    /// #[derive(Deserialize, Debug)]
+    /// struct User {
+    ///     uid: String,
+    ///     cn: String,
+    ///     sn: String,
+    /// }
+    ///
+    /// #[tokio::main]
+    /// async fn main(){
+    ///     let ldap_config = LdapConfig {
+    ///         bind_dn: Some(String::from("cn=manager")),
+    ///         bind_password: Some(String::from("password")),
+    ///         ldap_url: Url::parse("ldaps://localhost:1389/dc=example,dc=com").unwrap(),
+    ///         tls: TlsMode::Ldaps,
+    ///         tls_options: None,
+    ///         bind_method: BindMethod::Simple,
+    ///         dn_attribute: None,
+    ///         retry: None,
+    ///         operation_timeout: None,
+    ///         password_policy: false,
+    ///         connection_settings: None
+    ///     };
+    ///
+    ///     let mut client = LdapClient::new(ldap_config).await.unwrap();
+    ///
+    ///     let name_filter = ContainsFilter::from(String::from("cn"), String::from("J"));
+    ///     let sort = vec![
+    ///         SortBy {
+    ///             attribute: String::from("sn"),
+    ///             reverse: false
+    ///         }
+    ///     ];
+    ///
+    ///     let users = client.search_all::<_, _, _, User>(
+    ///         "ou=people,dc=example,dc=com",
+    ///         Scope::OneLevel,
+    ///         &name_filter,
+    ///         vec!["cn", "sn", "uid"],
+    ///         None,
+    ///         sort,
+    ///         MappingErrors::Fail,
+    ///     ).await.unwrap().entries;
+    /// }
+    /// ```
+    ///
+    #[allow(clippy::too_many_arguments)]
+    pub async fn search_all<F, A, S, T>(
+        &mut self,
+        base: &str,
+        scope: Scope,
+        filter: &F,
+        attributes: A,
+        page_size: Option<NonZeroU16>,
+        sort_by: Vec<SortBy>,
+        mapping_errors: MappingErrors,
+    ) -> Result<Entries<T>, Error>
+    where
+        F: Filter,
+        A: AsRef<[S]> + Send + Sync + Clone + fmt::Debug,
+        S: AsRef<str> + Send + Sync + Clone + fmt::Debug,
+        T: for<'de> serde::Deserialize<'de>,
+    {
+        let stream = self
+            .streaming_search_as(base, scope, filter, attributes, page_size, sort_by)
+            .await?;
+        let mut stream = Box::pin(stream);
+
+        let mut entries = Entries {
+            entries: Vec::new(),
+            errors: Vec::new(),
+            limit: None,
+        };
+        while let Some(entry) = stream.next().await {
+            match entry {
+                Ok(entry) => entries.entries.push(entry),
+                // Always the last item of the stream.
+                Err(Error::LimitExceeded(_, limit)) => entries.limit = Some(limit),
+                Err(error @ Error::Mapping(..)) if mapping_errors == MappingErrors::Collect => {
+                    entries.errors.push(error)
+                }
+                Err(error) => return Err(error),
+            }
+        }
+
+        Ok(entries)
+    }
+
+    ///
+    /// Search one page of records, to be continued later with the returned cookie.
+    ///
+    /// Unlike the paging of [`streaming_search`](Self::streaming_search), this hands the
+    /// Simple Paged Results cookie to the caller. So e.g. a stateless web service can return
+    /// one page of a large result set, and fetch the next one when it's asked for.
+    ///
+    /// Start with `None` as the cookie, and pass the [`Page::next_cookie`] of the previous page
+    /// to get the next one. Each page has to be requested with the same search arguments.
+    ///
+    ///
+    /// ## Connection pinning
+    ///
+    /// The cookie is tied to the connection that returned it. Most servers refuse it on another connection,
+    /// and all of them forget it when the connection closes. So:
+    ///
+    /// * Fetch all the pages with the same client.
+    /// * Only the first page is retried as configured in [`LdapConfig::retry`]. Reconnecting would
+    ///   invalidate the cookie, so the rest of the pages fail on a connection error.
+    ///   Start over from the first page then.
+    /// * With the pool, keep the client got from the pool for the whole paging,
+    ///   instead of getting a new one for each page. The pool gives out any of its connections.
+    ///   If the pages need to outlive a single request handler,
+    ///   it's best to use a dedicated `LdapClient` for the paging.
+    ///
+    /// The server holds the rest of the result set until the last page has been fetched,
+    /// the connection closes or the server's own time limit expires.
+    /// Use [`end_paged_search`](Self::end_paged_search) to release it when you don't need the rest of the pages.
+    ///
+    /// Search continuation references are skipped, regardless of [`SearchOptions::referrals`].
+    ///
+    ///
+    /// # Arguments
+    ///
+    /// * `base` - The base DN to search for the records
+    /// * `scope` - The scope of the search
+    /// * `filter` - The filter to search for the records
+    /// * `attributes` - The attributes to return from the search
+    /// * `page_size` - The maximum number of records in the page.
+    /// * `sort_by` - Sort the results using Server Side Sort LDAP extension.
+    /// * `cookie` - `None` for the first page, and the cookie of the previous page for the rest.
+    ///
+    ///
+    /// # Returns
+    ///
+    /// * `Result<Page<T>, Error>` - The records of the page, and the cookie for the next one if there are more.
+    ///   [`Error::Mapping`] if the server's paging response can't be decoded.
+    ///
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use simple_ldap::{
+    ///     BindMethod, LdapClient, LdapConfig, TlsMode,
+    ///     filter::EqFilter,
+    ///     ldap3::Scope,
+    /// };
+    /// use url::Url;
+    /// use serde::Deserialize;
+    /// use std::num::NonZeroU16;
+    ///
+    ///
+    /// #[derive(Deserialize, Debug)]
+    /// struct User {
+    ///     uid: String,
+    ///     cn: String,
+    /// }
+    ///
+    /// #[tokio::main]
+    /// async fn main(){
+    ///     let ldap_config = LdapConfig {
+    ///         bind_dn: Some(String::from("cn=manager")),
+    ///         bind_password: Some(String::from("password")),
+    ///         ldap_url: Url::parse("ldaps://localhost:1389/dc=example,dc=com").unwrap(),
+    ///         tls: TlsMode::Ldaps,
+    ///         tls_options: None,
+    ///         bind_method: BindMethod::Simple,
+    ///         dn_attribute: None,
+    ///         retry: None,
+    ///         operation_timeout: None,
+    ///         password_policy: false,
+    ///         connection_settings: None
+    ///     };
+    ///
+    ///     let mut client = LdapClient::new(ldap_config).await.unwrap();
+    ///
+    ///     let filter = EqFilter::from(String::from("objectClass"), String::from("person"));
+    ///     let page_size = NonZeroU16::new(50).unwrap();
+    ///
+    ///     let mut cookie = None;
+    ///     loop {
+    ///         let page = client.search_page::<_, _, _, User>(
+    ///             "ou=people,dc=example,dc=com",
+    ///             Scope::OneLevel,
+    ///             &filter,
+    ///             vec!["cn", "uid"],
+    ///             page_size,
+    ///             Vec::new(),
+    ///             cookie,
+    ///         ).await.unwrap();
+    ///
+    ///         println!("{:?}", page.items);
+    ///
+    ///         cookie = page.next_cookie;
+    ///         if cookie.is_none() {
+    ///             break;
+    ///         }
+    ///     }
+    /// }
+    /// ```
+    ///
+    #[allow(clippy::too_many_arguments)]
+    pub async fn search_page<F, A, S, T>(
+        &mut self,
+        base: &str,
+        scope: Scope,
+        filter: &F,
+        attributes: A,
+        page_size: NonZeroU16,
+        sort_by: Vec<SortBy>,
+        cookie: Option<Vec<u8>>,
+    ) -> Result<Page<T>, Error>
+    where
+        F: Filter,
+        A: AsRef<[S]> + Send + Sync + Clone + fmt::Debug,
+        S: AsRef<str> + Send + Sync + Clone + fmt::Debug,
+        T: for<'de> serde::Deserialize<'de>,
+    {
+        let (entries, result) = self
+            .paged_request(
+                base,
+                scope,
+                &filter.filter(),
+                attributes.as_ref(),
+                page_size.get().into(),
+                sort_by,
+                cookie,
+            )
+            .await?;
+
+        // An empty cookie means that this was the last page.
+        // A server not supporting paging returns everything at once, without the control.
+        let next_cookie = result
+            .ctrls
+            .into_iter()
+            .find_map(|control| match control {
+                Control(Some(ControlType::PagedResults), raw) => Some(raw),
+                _ => None,
+            })
+            .map(|raw| {
+                raw.val
+                    .as_deref()
+                    .and_then(parse_paging_cookie)
+                    .ok_or_else(|| {
+                        Error::Mapping(
+                            String::from("The server returned a malformed paged results response"),
+                            None,
+                        )
+                    })
+            })
+            .transpose()?
+            .and_then(vec_to_option);
+
+        let items = entries
+            .into_iter()
+            .map(|entry| {
+                Record {
+                    search_entry: SearchEntry::construct(entry),
+                }
+                .to_record()
+            })
+            .collect::<Result<_, _>>()?;
+
+        Ok(Page { items, next_cookie })
+    }
+
+    ///
+    /// End a paged search started with [`search_page`](Self::search_page), without fetching the rest of the pages.
+    ///
+    /// This asks the server to release the rest of the result set, by sending a page request of size zero.
+    /// It has to be done with the same client and the same search arguments as the pages.
+    ///
+    ///
+    /// # Arguments
+    ///
+    /// * `base` - The base DN of the search
+    /// * `scope` - The scope of the search
+    /// * `filter` - The filter of the search
+    /// * `attributes` - The attributes of the search
+    /// * `sort_by` - The sort of the search
+    /// * `cookie` - The [`Page::next_cookie`] of the last page fetched.
+    ///
+    ///
+    /// # Returns
+    ///
+    /// * `Result<(), Error>` - Error if the server refused the request, e.g. for an unknown cookie.
+    ///
+    pub async fn end_paged_search<F, A, S>(
+        &mut self,
+        base: &str,
+        scope: Scope,
+        filter: &F,
+        attributes: A,
+        sort_by: Vec<SortBy>,
+        cookie: Vec<u8>,
+    ) -> Result<(), Error>
+    where
+        F: Filter,
+        A: AsRef<[S]> + Send + Sync + Clone + fmt::Debug,
+        S: AsRef<str> + Send + Sync + Clone + fmt::Debug,
+    {
+        self.paged_request(
+            base,
+            scope,
+            &filter.filter(),
+            attributes.as_ref(),
+            0,
+            sort_by,
+            Some(cookie),
+        )
+        .await?;
+
+        debug!("Ended the paged search of {base:?}");
+        Ok(())
+    }
+
+    /// Search for a page of the given size, sending the paging control ourselves.
+    ///
+    /// Retried only without a cookie, as the cookie is tied to the connection.
+    #[allow(clippy::too_many_arguments)]
+    async fn paged_request<S: AsRef<str> + Send + Sync + Clone + fmt::Debug>(
+        &mut self,
+        base: &str,
+        scope: Scope,
+        filter: &str,
+        attributes: &[S],
+        size: i32,
+        sort_by: Vec<SortBy>,
+        cookie: Option<Vec<u8>>,
+    ) -> Result<(Vec<ResultEntry>, LdapResult), Error> {
+        let options = &self.search_options.clone();
+        let sort_by = &sort_by;
+        let continued = cookie.is_some();
+        let cookie = &cookie.unwrap_or_default();
+        let request = |mut ldap: Ldap| async move {
+            options.apply(&mut ldap);
+            // The adapter would fetch all the pages.
+            let mut request_controls = options.controls.clone();
+            request_controls.push(
+                controls::PagedResults {
+                    size,
+                    cookie: cookie.clone(),
+                }
+                .into(),
+            );
+            ldap.with_controls(request_controls);
+
+            let mut adapters = search_adapters(None, sort_by.clone())?;
+            adapters.push(Box::new(EntriesOnly::new()));
+
+            let (entries, result) =
+                collect_search(&mut ldap, adapters, base, scope, filter, attributes).await?;
+            let result = result.success().map_err(|error| {
+                search_error(format!("Error searching for a page: {error:?}"), error)
+            })?;
+
+            Ok((entries, result))
+        };
+
+        if continued {
+            request(self.operation_handle()).await
+        } else {
+            self.retrying(request).await
+        }
+    }
+
+    ///
+    /// Browse a window of a sorted list of entries, using the Virtual List View LDAP extension.
+    ///
+    /// This is for showing a large list a screenful at a time, without fetching the entries before it.
+    /// The window can be positioned either by an offset in the list, or by a value of the first sort key.
+    /// The server needs to support both Virtual List View and Server Side Sort.
+    ///
+    ///
+    /// # Arguments
+    ///
+    /// * `base` - The base DN to search for the records
+    /// * `scope` - The scope of the search
+    /// * `filter` - The filter to search for the records
+    /// * `attributes` - The attributes to return from the search
+    /// * `view` - How to sort the list and which part of it to return.
+    ///
+    ///
+    /// # Returns
+    ///
+    /// * `Result<Window<T>, Error>` - The entries of the window along with their position in the list.
+    ///   [`Error::VirtualListView`] if the server can't provide the window, e.g. if the offset is out of range.
+    ///
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use simple_ldap::{
+    ///     BindMethod, LdapClient, LdapConfig, ListView, SortBy, TlsMode, ViewTarget,
+    ///     filter::EqFilter,
+    ///     ldap3::Scope,
+    /// };
+    /// use url::Url;
+    /// use serde::Deserialize;
+    ///
+    ///
+    /// #[derive(Deserialize, Debug)]
+    /// struct User {
+    ///     uid: String,
+    ///     cn: String,
+    /// }
+    ///
+    /// #[tokio::main]
+    /// async fn main(){
+    ///     let ldap_config = LdapConfig {
+    ///         bind_dn: Some(String::from("cn=manager")),
+    ///         bind_password: Some(String::from("password")),
+    ///         ldap_url: Url::parse("ldaps://localhost:1389/dc=example,dc=com").unwrap(),
+    ///         tls: TlsMode::Ldaps,
+    ///         tls_options: None,
+    ///         bind_method: BindMethod::Simple,
+    ///         dn_attribute: None,
+    ///         retry: None,
+    ///         operation_timeout: None,
+    ///         password_policy: false,
+    ///         connection_settings: None
+    ///     };
+    ///
+    ///     let mut client = LdapClient::new(ldap_config).await.unwrap();
+    ///
+    ///     let filter = EqFilter::from(String::from("objectClass"), String::from("person"));
+    ///     let view = ListView {
+    ///         sort_by: vec![SortBy { attribute: String::from("cn"), reverse: false }],
+    ///         // The first 20 people whose name starts with M or later.
+    ///         target: ViewTarget::GreaterThanOrEqual(String::from("M")),
+    ///         before_count: 0,
+    ///         after_count: 19,
+    ///         context_id: None,
+    ///     };
+    ///
+    ///     let window = client.browse::<_, _, _, User>(
+    ///         "ou=people,dc=example,dc=com",
+    ///         Scope::OneLevel,
+    ///         &filter,
+    ///         vec!["cn", "uid"],
+    ///         view,
+    ///     ).await.unwrap();
+    ///
+    ///     println!("At {} of {}", window.target_position, window.content_count);
+    /// }
+    /// ```
+    ///
+    pub async fn browse<F, A, S, T>(
+        &mut self,
+        base: &str,
+        scope: Scope,
+        filter: &F,
+        attributes: A,
+        view: ListView,
+    ) -> Result<Window<T>, Error>
+    where
+        F: Filter,
+        A: AsRef<[S]> + Send + Sync + Clone + fmt::Debug,
+        S: AsRef<str> + Send + Sync + Clone + fmt::Debug,
+        T: for<'de> serde::Deserialize<'de>,
+    {
+        if view.sort_by.is_empty() {
+            return Err(Error::VirtualListView(String::from(
+                "Virtual List View needs something to sort by",
+            )));
+        }
+
+        let filter = &filter.filter();
+        let attributes = attributes.as_ref();
+        let options = &self.search_options.clone();
+        let view = &view;
+        let (entries, response) = self
+            .retrying(|mut ldap| async move {
+                options.apply(&mut ldap);
+                let sort = ServerSideSort::new(view.sort_by.clone())
+                    .map_err(|duplicate_args_err| Error::Sort(duplicate_args_err.to_string()))?;
+                let adapters: Vec<Box<dyn Adapter<_, _>>> = vec![
+                    // Sort needs to be first, the view is a window of the sorted list.
+                    Box::new(sort),
+                    Box::new(VirtualListView::new(view)),
+                    Box::new(EntriesOnly::new()),
+                ];
+
+                let (entries, result) = collect_search(
+                    &mut ldap,
+                    adapters,
+                    base,
+                    scope,
+                    filter.as_str(),
+                    attributes,
+                )
+                .await?;
+
+                // The view result tells more about the failure than the plain result code.
+                let response = vlv::adapter::get_response_control(&result.ctrls)?;
+                if let Some(response) = &response
+                    && response.result != VirtualListViewResult::Success
+                {
+                    return Err(Error::VirtualListView(format!(
+                        "The server couldn't provide the view: {:?}, {}",
+                        response.result, result.text
+                    )));
+                }
+
+                result.success().map_err(|error| {
+                    search_error(format!("Error browsing the records: {error:?}"), error)
+                })?;
+
+                let response = response.ok_or_else(|| {
+                    Error::VirtualListView(String::from(
+                        "The server didn't return the Virtual List View response",
+                    ))
+                })?;
+
+                Ok((entries, response))
+            })
+            .await?;
+
+        let entries = entries
+            .into_iter()
+            .map(|entry| {
+                Record {
+                    search_entry: SearchEntry::construct(entry),
+                }
+                .to_record()
+            })
+            .collect::<Result<_, _>>()?;
+
+        Ok(Window {
+            entries,
+            target_position: response.target_position,
+            content_count: response.content_count,
+            context_id: response.context_id,
+        })
+    }
+
+    ///
+    /// Create a new record in the LDAP server. The record will be created in the provided base DN.
+    ///
+    /// # Arguments
+    ///
+    /// * `uid` - The uid of the record
+    /// * `base` - The base DN to create the record
+    /// * `data` - The attributes of the record
+    ///
+    ///
+    /// # Returns
+    ///
+    /// * `Result<(), Error>` - Returns an error if the record creation fails
+    ///
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use simple_ldap::{BindMethod, LdapClient, LdapConfig, TlsMode};
+    /// use url::Url;
+    /// use std::collections::HashSet;
+    ///
+    /// #[tokio::main]
+    /// async fn main(){
+    ///     let ldap_config = LdapConfig {
+    ///         bind_dn: Some(String::from("cn=manager")),
+    ///         bind_password: Some(String::from("password")),
+    ///         ldap_url: Url::parse("ldaps://localhost:1389/dc=example,dc=com").unwrap(),
+    ///         tls: TlsMode::Ldaps,
+    ///         tls_options: None,
+    ///         bind_method: BindMethod::Simple,
+    ///         dn_attribute: None,
+    ///         retry: None,
+    ///         operation_timeout: None,
+    ///         password_policy: false,
+    ///         connection_settings: None
+    ///     };
+    ///
+    ///     let mut client = LdapClient::new(ldap_config).await.unwrap();
+    ///
+    ///     let data = vec![
+    ///         ( "objectClass",HashSet::from(["organizationalPerson", "inetorgperson", "top", "person"]),),
+    ///         ("uid",HashSet::from(["bd9b91ec-7a69-4166-bf67-cc7e553b2fd9"]),),
+    ///         ("cn", HashSet::from(["Kasun"])),
+    ///         ("sn", HashSet::from(["Ranasingh"])),
+    ///     ];
+    ///
+    ///     let result = client.create("bd9b91ec-7a69-4166-bf67-cc7e553b2fd9", "ou=people,dc=example,dc=com", data).await;
+    /// }
+    /// ```
+    ///
+    pub async fn create(
+        &mut self,
+        uid: &str,
+        base: &str,
+        data: Vec<(&str, HashSet<&str>)>,
+    ) -> Result<(), Error> {
+        let dn = format!("uid={uid},{base}");
+        self.reconnecting(async |mut ldap| {
+            let save = ldap.add(dn.as_str(), data).await;
+            let save = check_timeout(&mut ldap, save).await?;
+            if let Err(err) = save {
+                return Err(Error::Create(
+                    format!("Error saving record: {err:?}"),
+                    Box::new(err),
+                ));
+            }
+            let save = save.unwrap().success();
+
+            if let Err(err) = save {
+                return Err(Error::Create(
+                    format!("Error saving record: {err:?}"),
+                    Box::new(err),
+                ));
+            }
+            let res = save.unwrap();
+            debug!("Successfully created record result: {:?}", res);
+            Ok(())
+        })
+        .await
+    }
+
+    ///
+    /// Update a record in the LDAP server. The record will be updated in the provided base DN.
+    ///
+    /// # Arguments
+    ///
+    /// * `uid` - The uid of the record
+    /// * `base` - The base DN to update the record
+    /// * `data` - The attributes of the record
+    /// * `new_uid` - The new uid of the record. If the new uid is provided, the uid of the record will be updated.
+    ///
+    ///
+    /// # Returns
+    ///
+    /// * `Result<(), Error>` - Returns an error if the record update fails
+    ///
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use simple_ldap::{
+    ///     BindMethod, LdapClient, LdapConfig, TlsMode,
+    ///     ldap3::Mod
+    /// };
+    /// use url::Url;
+    /// use std::collections::HashSet;
+    ///
+    /// #[tokio::main]
+    /// async fn main(){
+    ///     let ldap_config = LdapConfig {
+    ///         bind_dn: Some(String::from("cn=manager")),
+    ///         bind_password: Some(String::from("password")),
+    ///         ldap_url: Url::parse("ldaps://localhost:1389/dc=example,dc=com").unwrap(),
+    ///         tls: TlsMode::Ldaps,
+    ///         tls_options: None,
+    ///         bind_method: BindMethod::Simple,
+    ///         dn_attribute: None,
+    ///         retry: None,
+    ///         operation_timeout: None,
+    ///         password_policy: false,
+    ///         connection_settings: None
+    ///     };
+    ///
+    ///     let mut client = LdapClient::new(ldap_config).await.unwrap();
+    ///
+    ///     let data = vec![
+    ///         Mod::Replace("cn", HashSet::from(["Jhon_Update"])),
+    ///         Mod::Replace("sn", HashSet::from(["Eliet_Update"])),
+    ///     ];
+    ///
+    ///     let result = client.update(
+    ///         "e219fbc0-6df5-4bc3-a6ee-986843bb157e",
+    ///         "ou=people,dc=example,dc=com",
+    ///         data,
+    ///         None
+    ///     ).await;
+    /// }
+    /// ```
+    ///
+    pub async fn update(
+        &mut self,
+        uid: &str,
+        base: &str,
+        data: Vec<Mod<&str>>,
+        new_uid: Option<&str>,
+    ) -> Result<(), Error> {
+        let dn = format!("uid={uid},{base}");
+
+        self.reconnecting(async |mut ldap| {
+            let res = ldap.modify(dn.as_str(), data).await;
+            let res = check_timeout(&mut ldap, res).await?;
+            if let Err(err) = res {
+                return Err(Error::Update(
+                    format!("Error updating record: {err:?}"),
+                    Box::new(err),
+                ));
+            }
+
+            let res = res.unwrap().success();
+            if let Err(err) = res {
+                match err {
+                    LdapError::LdapResult { result } => {
+                        if result.rc == NO_SUCH_RECORD {
+                            return Err(Error::NotFound(format!(
+                                "No records found for the uid: {uid:?}"
+                            )));
+                        }
+                    }
+                    _ => {
+                        return Err(Error::Update(
+                            format!("Error updating record: {err:?}"),
+                            Box::new(err),
+                        ));
+                    }
+                }
+            }
+
+            if new_uid.is_none() {
+                return Ok(());
+            }
+
+            let new_uid = new_uid.unwrap();
+            if !uid.eq_ignore_ascii_case(new_uid) {
+                let new_dn = format!("uid={new_uid}");
+                let dn_update = ldap
+                    .modifydn(dn.as_str(), new_dn.as_str(), true, None)
+                    .await;
+                let dn_update = check_timeout(&mut ldap, dn_update).await?;
+                if let Err(err) = dn_update {
+                    error!("Failed to update dn for record {:?} error {:?}", uid, err);
+                    return Err(Error::Update(
+                        format!("Failed to update dn for record {uid:?}"),
+                        Box::new(err),
+                    ));
+                }
+
+                let dn_update = dn_update.unwrap().success();
+                if let Err(err) = dn_update {
+                    error!("Failed to update dn for record {:?} error {:?}", uid, err);
+                    return Err(Error::Update(
+                        format!("Failed to update dn for record {uid:?}"),
+                        Box::new(err),
+                    ));
+                }
+
+                let res = dn_update.unwrap();
+                debug!("Successfully updated dn result: {:?}", res);
+            }
+
+            Ok(())
+        })
+        .await
+    }
+
+    ///
+    /// Rename an entry and/or move it under a new parent.
+    /// Moving an entry with children moves the whole subtree, if the server supports it.
+    ///
+    /// # Arguments
+    ///
+    /// * `dn` - The dn of the entry
+    /// * `new_rdn` - The new relative dn of the entry, e.g. "uid=new". Pass the current one to just move the entry.
+    /// * `new_parent` - The dn of the new parent. `None` keeps the entry under its current parent.
+    /// * `delete_old_rdn` - Remove the old RDN value from the entry's attributes.
+    ///
+    ///
+    /// # Returns
+    ///
+    /// * `Result<SimpleDN, Error>` - The dn of the entry after the operation.
+    ///   [`Error::InvalidDn`] if that can't be represented as a [`SimpleDN`], in which case nothing is renamed.
+    ///
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use simple_ldap::{BindMethod, LdapClient, LdapConfig, TlsMode};
+    /// use url::Url;
+    ///
+    /// #[tokio::main]
+    /// async fn main(){
+    ///     let ldap_config = LdapConfig {
+    ///         bind_dn: Some(String::from("cn=manager")),
+    ///         bind_password: Some(String::from("password")),
+    ///         ldap_url: Url::parse("ldaps://localhost:1389/dc=example,dc=com").unwrap(),
+    ///         tls: TlsMode::Ldaps,
+    ///         tls_options: None,
+    ///         bind_method: BindMethod::Simple,
+    ///         dn_attribute: None,
+    ///         retry: None,
+    ///         operation_timeout: None,
+    ///         password_policy: false,
+    ///         connection_settings: None
+    ///     };
+    ///
+    ///     let mut client = LdapClient::new(ldap_config).await.unwrap();
+    ///
+    ///     let new_dn = client.rename(
+    ///         "uid=e219fbc0-6df5-4bc3-a6ee-986843bb157e,ou=people,dc=example,dc=com",
+    ///         "uid=e219fbc0-6df5-4bc3-a6ee-986843bb157e",
+    ///         Some("ou=disabled,dc=example,dc=com"),
+    ///         true
+    ///     ).await;
+    /// }
+    /// ```
+    pub async fn rename(
+        &mut self,
+        dn: &str,
+        new_rdn: &str,
+        new_parent: Option<&str>,
+        delete_old_rdn: bool,
+    ) -> Result<SimpleDN, Error> {
+        // The DNs are passed to the server as is, only the resulting one is figured out here.
+        // Splitting the RDN off works for any DN, even the ones `SimpleDN` can't represent.
+        let new_dn = match new_parent.or_else(|| simple_dn::split_rdn(dn).1) {
+            Some(parent) => format!("{new_rdn},{parent}"),
+            // Renaming a top level entry.
+            None => new_rdn.to_owned(),
+        };
+        // Checked before renaming, so that a failure here doesn't leave the caller guessing.
+        let new_dn = parse_dn(&new_dn)?;
+
+        self.reconnecting(async |mut ldap| {
+            let res = ldap.modifydn(dn, new_rdn, delete_old_rdn, new_parent).await;
+            let res = check_timeout(&mut ldap, res)
+                .await?
+                .map_err(|err| Error::Update(format!("Failed to rename {dn:?}"), Box::new(err)))?;
+
+            match res.success() {
+                Ok(res) => {
+                    debug!("Successfully renamed {dn:?} to {new_dn:?}: {res:?}");
+                    Ok(new_dn)
+                }
+                Err(LdapError::LdapResult { result }) if result.rc == NO_SUCH_RECORD => Err(
+                    Error::NotFound(format!("No records found for the dn: {dn:?}")),
+                ),
+                Err(err) => {
+                    error!("Failed to rename {:?} error {:?}", dn, err);
+                    Err(Error::Update(
+                        format!("Failed to rename {dn:?}"),
+                        Box::new(err),
+                    ))
+                }
+            }
+        })
+        .await
+    }
+
+    ///
+    /// Move an entry under a new parent, keeping its RDN.
+    ///
+    /// This is a shorthand for [`rename`](Self::rename).
+    ///
+    /// # Arguments
+    ///
+    /// * `dn` - The dn of the entry
+    /// * `new_parent` - The dn of the new parent
+    ///
+    ///
+    /// # Returns
+    ///
+    /// * `Result<SimpleDN, Error>` - The dn of the entry after the move.
+    ///   [`Error::InvalidDn`] if that can't be represented as a [`SimpleDN`], in which case nothing is moved.
+    ///
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use simple_ldap::{BindMethod, LdapClient, LdapConfig, TlsMode};
+    /// use url::Url;
+    ///
+    /// #[tokio::main]
+    /// async fn main(){
+    ///     let ldap_config = LdapConfig {
+    ///         bind_dn: Some(String::from("cn=manager")),
+    ///         bind_password: Some(String::from("password")),
+    ///         ldap_url: Url::parse("ldaps://localhost:1389/dc=example,dc=com").unwrap(),
+    ///         tls: TlsMode::Ldaps,
+    ///         tls_options: None,
+    ///         bind_method: BindMethod::Simple,
+    ///         dn_attribute: None,
+    ///         retry: None,
+    ///         operation_timeout: None,
+    ///         password_policy: false,
+    ///         connection_settings: None
+    ///     };
+    ///
+    ///     let mut client = LdapClient::new(ldap_config).await.unwrap();
+    ///
+    ///     let new_dn = client.move_entry(
+    ///         "uid=e219fbc0-6df5-4bc3-a6ee-986843bb157e,ou=people,dc=example,dc=com",
+    ///         "ou=disabled,dc=example,dc=com"
+    ///     ).await;
+    /// }
+    /// ```
+    pub async fn move_entry(&mut self, dn: &str, new_parent: &str) -> Result<SimpleDN, Error> {
+        let (rdn, _) = simple_dn::split_rdn(dn);
+        // The RDN stays the same, so there's no old value to delete.
+        self.rename(dn, rdn, Some(new_parent), false).await
+    }
+
+    ///
+    /// Delete a record in the LDAP server. The record will be deleted in the provided base DN.
+    ///
+    /// # Arguments
+    ///
+    /// * `uid` - The uid of the record
+    /// * `base` - The base DN to delete the record
+    ///
+    ///
+    /// # Returns
+    ///
+    /// * `Result<(), Error>` - Returns an error if the record delete fails
+    ///
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use simple_ldap::{BindMethod, LdapClient, LdapConfig, TlsMode};
+    /// use url::Url;
+    ///
+    /// #[tokio::main]
+    /// async fn main(){
+    ///     let ldap_config = LdapConfig {
+    ///         bind_dn: Some(String::from("cn=manager")),
+    ///         bind_password: Some(String::from("password")),
+    ///         ldap_url: Url::parse("ldaps://localhost:1389/dc=example,dc=com").unwrap(),
+    ///         tls: TlsMode::Ldaps,
+    ///         tls_options: None,
+    ///         bind_method: BindMethod::Simple,
+    ///         dn_attribute: None,
+    ///         retry: None,
+    ///         operation_timeout: None,
+    ///         password_policy: false,
+    ///         connection_settings: None
+    ///     };
+    ///
+    ///     let mut client = LdapClient::new(ldap_config).await.unwrap();
+    ///
+    ///     let result = client.delete("e219fbc0-6df5-4bc3-a6ee-986843bb157e", "ou=people,dc=example,dc=com").await;
+    /// }
+    /// ```
+    pub async fn delete(&mut self, uid: &str, base: &str) -> Result<(), Error> {
+        let dn = format!("uid={uid},{base}");
+        self.reconnecting(async |mut ldap| {
+            let delete = ldap.delete(dn.as_str()).await;
+            let delete = check_timeout(&mut ldap, delete).await?;
+
+            if let Err(err) = delete {
+                return Err(Error::Delete(
+                    format!("Error deleting record: {err:?}"),
+                    Box::new(err),
+                ));
+            }
+            let delete = delete.unwrap().success();
+            if let Err(err) = delete {
+                match err {
+                    LdapError::LdapResult { result } => {
+                        if result.rc == NO_SUCH_RECORD {
+                            return Err(Error::NotFound(format!(
+                                "No records found for the uid: {uid:?}"
+                            )));
+                        }
+                    }
+                    _ => {
+                        return Err(Error::Delete(
+                            format!("Error deleting record: {err:?}"),
+                            Box::new(err),
+                        ));
+                    }
+                }
+            }
+            debug!("Successfully deleted record result: {:?}", uid);
+            Ok(())
+        })
+        .await
+    }
+
+    ///
+    /// Delete an entry together with everything below it.
+    ///
+    /// If the server advertises the Tree Delete control, the server does the deletion in one go.
+    /// Otherwise the subtree is deleted entry by entry, leaves first.
+    ///
+    /// # Arguments
+    ///
+    /// * `dn` - The dn of the subtree root
+    /// * `options` - Concurrency and dry-run settings
+    ///
+    ///
+    /// # Returns
+    ///
+    /// * `Result<Vec<String>, Error>` - The DNs of the deleted entries (or the ones that would be deleted
+    ///   in a dry-run), children before their parents.
+    ///   With the Tree Delete control this is just the root DN, as the subtree isn't listed beforehand
+    ///   and the server doesn't tell what it deleted.
+    ///
+    ///
+    /// # Partial deletes
+    ///
+    /// If a delete fails midway in the client side fallback, the entries deleted before it remain deleted.
+    /// The operation is not atomic, and neither is it isolated from concurrent changes in the subtree.
+    ///
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use simple_ldap::{BindMethod, DeleteSubtreeOptions, LdapClient, LdapConfig, TlsMode};
+    /// use url::Url;
+    ///
+    /// #[tokio::main]
+    /// async fn main(){
+    ///     let ldap_config = LdapConfig {
+    ///         bind_dn: Some(String::from("cn=manager")),
+    ///         bind_password: Some(String::from("password")),
     ///         ldap_url: Url::parse("ldaps://localhost:1389/dc=example,dc=com").unwrap(),
+    ///         tls: TlsMode::Ldaps,
+    ///         tls_options: None,
+    ///         bind_method: BindMethod::Simple,
     ///         dn_attribute: None,
+    ///         retry: None,
+    ///         operation_timeout: None,
+    ///         password_policy: false,
     ///         connection_settings: None
     ///     };
     ///
     ///     let mut client = LdapClient::new(ldap_config).await.unwrap();
     ///
-    ///     let name_filter = EqFilter::from(String::from("cn"), String::from("Sam"));
-    ///     let attributes = vec!["cn", "sn", "uid"];
-    ///     let sort = vec![
-    ///         SortBy {
-    ///             attribute: String::from("sn"),
-    ///             reverse: true
-    ///         }
-    ///     ];
-    ///
-    ///     let stream = client.streaming_search(
-    ///         "ou=people,dc=example,dc=com",
-    ///         Scope::OneLevel,
-    ///         &name_filter,
-    ///         attributes,
-    ///         Some(NonZero::new(200).unwrap()), // The pagesize
-    ///         sort
-    ///     ).await.unwrap();
+    ///     // First have a look at what's going to happen.
+    ///     let dry_run = DeleteSubtreeOptions {
+    ///         dry_run: true,
+    ///         ..Default::default()
+    ///     };
+    ///     let doomed = client.delete_subtree("ou=old,dc=example,dc=com", dry_run).await.unwrap();
+    ///     println!("Deleting {doomed:?}");
     ///
-    ///     // Map the search results to User type.
-    ///     stream.and_then(async |record| record.to_record())
-    ///          // Do something with the Users concurrently.
-    ///         .try_for_each(async |user: User| {
-    ///             println!("User: {:?}", user);
-    ///             Ok(())
-    ///         })
-    ///         .await
-    ///         .unwrap();
+    ///     let result = client.delete_subtree("ou=old,dc=example,dc=com", DeleteSubtreeOptions::default()).await;
     /// }
     /// ```
-    ///
-    pub async fn streaming_search<'a, F, A, S>(
-        // This self reference  lifetime has some nuance behind it.
-        //
-        // In principle it could just be a value, but then you wouldn't be able to call this
-        // with a pooled client, as the deadpool `Object` wrapper only ever gives out references.
-        //
-        // The lifetime is needed to guarantee that the client is not returned to the pool before
-        // the returned stream is finished. This requirement is artificial. Internally the `ldap3` client
-        // just makes copy. So this lifetime is here just to enforce correct pool usage.
-        &'a mut self,
-        base: &str,
-        scope: Scope,
-        filter: &F,
-        attributes: A,
-        // The internal adapter takes i32, but half of its range is invalid.
-        page_size: Option<NonZeroU16>,
-        sort_by: Vec<SortBy>,
-    ) -> Result<impl Stream<Item = Result<Record, Error>> + use<'a, F, A, S>, Error>
-    where
-        F: Filter,
-        // PagedResults requires Clone and Debug too.
-        A: AsRef<[S]> + Send + Sync + Clone + fmt::Debug + 'a,
-        S: AsRef<str> + Send + Sync + Clone + fmt::Debug + 'a,
-    {
-        // Define the needed adapters.
+    pub async fn delete_subtree(
+        &mut self,
+        dn: &str,
+        options: DeleteSubtreeOptions,
+    ) -> Result<Vec<String>, Error> {
+        if options.dry_run {
+            let subtree = self.list_subtree(dn).await?;
+            return Ok(subtree.into_iter().flatten().collect());
+        }
 
-        // Entries only is only needed with paging.
-        let (paging_adapter, entries_only_adapter) = page_size
-            .map(|non_zero| (PagedResults::new(non_zero.get().into()), EntriesOnly::new()))
-            .map(|(page_adapter, entries_adapter)| {
-                (Box::new(page_adapter) as _, Box::new(entries_adapter) as _)
-            })
-            .unzip();
-
-        // Empty vec just means that we won't use the search adapter.
-        let sort_adapter: Option<Box<dyn Adapter<'a, S, A>>> = vec_to_option(sort_by)
-            .map(ServerSideSort::new)
-            .transpose()
-            .map_err(|duplicate_args_err| Error::Sort(duplicate_args_err.to_string()))?
-            .map(|adapter| Box::new(adapter) as _);
-
-        let maybe_adapters: Vec<Option<Box<dyn Adapter<'a, S, A>>>> = vec![
-            // Sort needs to be before paging, so that it's control will be included in all the page requests.
-            sort_adapter,
-            entries_only_adapter,
-            paging_adapter,
-        ];
+        // The writes aren't repeated, but a lost connection is replaced.
+        let deleted: Result<Vec<String>, Error> = async {
+            let deleted = if self.supports_control(TREE_DELETE_OID).await? {
+                debug!("Deleting subtree {dn:?} with the Tree Delete control.");
+
+                let tree_delete = RawControl {
+                    ctype: TREE_DELETE_OID.to_owned(),
+                    crit: true,
+                    val: None,
+                };
+                let mut ldap = self.operation_handle();
+                let delete = ldap.with_controls(tree_delete).delete(dn).await;
+                let delete = check_timeout(&mut ldap, delete).await?.map_err(|err| {
+                    Error::Delete(format!("Error deleting subtree {dn:?}"), Box::new(err))
+                })?;
+                delete_result_to_error(delete, dn)?;
+
+                vec![dn.to_owned()]
+            } else {
+                debug!(
+                    "Tree Delete control not supported. Deleting subtree {dn:?} entry by entry."
+                );
+
+                let subtree = self.list_subtree(dn).await?;
+
+                // All the entries at the same depth are independent of each other, and can be deleted concurrently.
+                for level in &subtree {
+                    futures::stream::iter(level)
+                        .map(|entry_dn| {
+                            let mut ldap = self.operation_handle();
+                            async move {
+                                let delete = ldap.delete(entry_dn).await;
+                                let delete =
+                                    check_timeout(&mut ldap, delete).await?.map_err(|err| {
+                                        Error::Delete(
+                                            format!("Error deleting record {entry_dn:?}"),
+                                            Box::new(err),
+                                        )
+                                    })?;
+                                delete_result_to_error(delete, entry_dn)
+                            }
+                        })
+                        .buffer_unordered(options.concurrency.get())
+                        .try_collect::<()>()
+                        .await?;
+                }
 
-        // This might end up as no adapters but that's perfectly fine too.
-        // Internally the non adapted streaming search would anyway just call the same thing with an empty adapter list.
-        let adapters: Vec<_> = maybe_adapters.into_iter().flatten().collect();
+                subtree.into_iter().flatten().collect()
+            };
 
-        let search_stream = self
-            .ldap
-            .streaming_search_with(adapters, base, scope, filter.filter().as_str(), attributes)
-            .await
-            .map_err(|ldap_error| {
-                Error::Query(
-                    format!("Error searching for record: {ldap_error:?}"),
-                    ldap_error,
-                )
-            })?;
+            Ok(deleted)
+        }
+        .await;
+        let deleted = self.recover(deleted).await?;
+
+        debug!("Successfully deleted subtree {dn:?}");
+        Ok(deleted)
+    }
+
+    /// Get all the DNs in a subtree, grouped by depth. The deepest first.
+    async fn list_subtree(&mut self, dn: &str) -> Result<Vec<Vec<String>>, Error> {
+        // Presence filter, matching everything.
+        let everything = EqFilter::from(String::from("objectClass"), String::from("*"));
+        // Special attribute list meaning "no attributes". We only need the DNs.
+        let no_attributes = vec!["1.1"];
+        // Limits would leave parts of the subtree in place.
+        let mut client = self.with_search_options(SearchOptions::default());
+
+        let stream = client
+            .streaming_search(
+                dn,
+                Scope::Subtree,
+                &everything,
+                no_attributes,
+                Some(SUBTREE_LISTING_PAGE_SIZE),
+                Vec::new(),
+            )
+            .await?;
+
+        let mut dns: Vec<String> = stream
+            .map_ok(|record| record.search_entry.dn)
+            .try_collect()
+            .await?;
+
+        if dns.is_empty() {
+            return Err(Error::NotFound(format!(
+                "No records found for the dn: {dn:?}"
+            )));
+        }
+
+        // Deepest first.
+        dns.sort_by_key(|dn| Reverse(simple_dn::rdn_count(dn)));
+
+        let levels = dns
+            .into_iter()
+            .chunk_by(|dn| simple_dn::rdn_count(dn))
+            .into_iter()
+            .map(|(_, level)| level.collect())
+            .collect();
+
+        Ok(levels)
+    }
+
+    /// Check whether the server advertises support for the given control in its root DSE.
+    async fn supports_control(&mut self, oid: &str) -> Result<bool, Error> {
+        let mut ldap = self.operation_handle();
+        let (entries, _) = search_entries(
+            &mut ldap,
+            "",
+            Scope::Base,
+            "(objectClass=*)",
+            vec!["supportedControl"],
+        )
+        .await?
+        .and_then(|search| search.success())
+        .map_err(|err| {
+            Error::Query(
+                format!("Error reading the root DSE: {err:?}"),
+                Box::new(err),
+            )
+        })?;
 
-        to_native_stream(search_stream)
+        let supported = entries
+            .into_iter()
+            .map(SearchEntry::construct)
+            .flat_map(|entry| entry.attrs.into_values())
+            .flatten()
+            .any(|supported_oid| supported_oid == oid);
+
+        Ok(supported)
     }
 
     ///
-    /// Create a new record in the LDAP server. The record will be created in the provided base DN.
+    /// Compare an attribute value of an entry in the LDAP server.
+    /// The server tells whether the entry has the attribute with the given value,
+    /// without returning the value itself.
+    ///
+    /// This is useful e.g. for checking group membership or a flag, when the server
+    /// doesn't grant read access to the attribute, but allows comparing it.
     ///
     /// # Arguments
     ///
-    /// * `uid` - The uid of the record
-    /// * `base` - The base DN to create the record
-    /// * `data` - The attributes of the record
+    /// * `dn` - The dn of the entry
+    /// * `attribute` - The attribute to compare
+    /// * `value` - The value to compare against
     ///
     ///
     /// # Returns
     ///
-    /// * `Result<(), Error>` - Returns an error if the record creation fails
+    /// * `Result<bool, Error>` - Whether any of the attribute's values matched
     ///
     ///
     /// # Example
     ///
     /// ```no_run
-    /// use simple_ldap::{LdapClient, LdapConfig};
+    /// use simple_ldap::{BindMethod, LdapClient, LdapConfig, TlsMode};
     /// use url::Url;
-    /// use std::collections::HashSet;
     ///
     /// #[tokio::main]
     /// async fn main(){
     ///     let ldap_config = LdapConfig {
-    ///         bind_dn: String::from("cn=manager"),
-    ///         bind_password: String::from("password"),
+    ///         bind_dn: Some(String::from("cn=manager")),
+    ///         bind_password: Some(String::from("password")),
     ///         ldap_url: Url::parse("ldaps://localhost:1389/dc=example,dc=com").unwrap(),
+    ///         tls: TlsMode::Ldaps,
+    ///         tls_options: None,
+    ///         bind_method: BindMethod::Simple,
     ///         dn_attribute: None,
+    ///         retry: None,
+    ///         operation_timeout: None,
+    ///         password_policy: false,
     ///         connection_settings: None
     ///     };
     ///
     ///     let mut client = LdapClient::new(ldap_config).await.unwrap();
     ///
-    ///     let data = vec![
-    ///         ( "objectClass",HashSet::from(["organizationalPerson", "inetorgperson", "top", "person"]),),
-    ///         ("uid",HashSet::from(["bd9b91ec-7a69-4166-bf67-cc7e553b2fd9"]),),
-    ///         ("cn", HashSet::from(["Kasun"])),
-    ///         ("sn", HashSet::from(["Ranasingh"])),
-    ///     ];
-    ///
-    ///     let result = client.create("bd9b91ec-7a69-4166-bf67-cc7e553b2fd9", "ou=people,dc=example,dc=com", data).await;
+    ///     let is_member = client.compare(
+    ///         "cn=test_group,ou=groups,dc=example,dc=com",
+    ///         "member",
+    ///         "uid=bd9b91ec-7a69-4166-bf67-cc7e553b2fd9,ou=people,dc=example,dc=com"
+    ///     ).await;
     /// }
     /// ```
+    pub async fn compare(&mut self, dn: &str, attribute: &str, value: &str) -> Result<bool, Error> {
+        self.compare_inner(dn, attribute, value.as_bytes()).await
+    }
+
     ///
-    pub async fn create(
+    /// Like [`compare`](Self::compare) but for binary valued attributes.
+    ///
+    /// # Arguments
+    ///
+    /// * `dn` - The dn of the entry
+    /// * `attribute` - The attribute to compare
+    /// * `value` - The raw bytes to compare against
+    ///
+    ///
+    /// # Returns
+    ///
+    /// * `Result<bool, Error>` - Whether any of the attribute's values matched
+    pub async fn compare_binary(
         &mut self,
-        uid: &str,
-        base: &str,
-        data: Vec<(&str, HashSet<&str>)>,
-    ) -> Result<(), Error> {
-        let dn = format!("uid={uid},{base}");
-        let save = self.ldap.add(dn.as_str(), data).await;
-        if let Err(err) = save {
-            return Err(Error::Create(format!("Error saving record: {err:?}"), err));
-        }
-        let save = save.unwrap().success();
+        dn: &str,
+        attribute: &str,
+        value: &[u8],
+    ) -> Result<bool, Error> {
+        self.compare_inner(dn, attribute, value).await
+    }
 
-        if let Err(err) = save {
-            return Err(Error::Create(format!("Error saving record: {err:?}"), err));
+    async fn compare_inner(
+        &mut self,
+        dn: &str,
+        attribute: &str,
+        value: &[u8],
+    ) -> Result<bool, Error> {
+        let compare = self
+            .retrying(|mut ldap| async move {
+                let compare = ldap.compare(dn, attribute, value).await;
+                check_timeout(&mut ldap, compare).await?.map_err(|err| {
+                    Error::Compare(format!("Error comparing record: {err:?}"), Box::new(err))
+                })
+            })
+            .await?;
+
+        match compare.equal() {
+            Ok(equal) => Ok(equal),
+            Err(LdapError::LdapResult { result }) if result.rc == NO_SUCH_RECORD => Err(
+                Error::NotFound(format!("No records found for the dn: {dn:?}")),
+            ),
+            Err(err) => Err(Error::Compare(
+                format!("Error comparing record: {err:?}"),
+                Box::new(err),
+            )),
         }
-        let res = save.unwrap();
-        debug!("Successfully created record result: {:?}", res);
-        Ok(())
     }
 
     ///
-    /// Update a record in the LDAP server. The record will be updated in the provided base DN.
+    /// Change a user's password using the Password Modify extended operation ([RFC 3062](https://datatracker.ietf.org/doc/html/rfc3062)).
+    ///
+    /// Unlike replacing the `userPassword` attribute with [`update`](Self::update), this lets the server
+    /// hash the password and enforce its password policy.
     ///
     /// # Arguments
     ///
-    /// * `uid` - The uid of the record
-    /// * `base` - The base DN to update the record
-    /// * `data` - The attributes of the record
-    /// * `new_uid` - The new uid of the record. If the new uid is provided, the uid of the record will be updated.
+    /// * `user_dn` - The dn of the user
+    /// * `old_password` - The current password of the user. Servers usually require this when users change their own passwords.
+    /// * `new_password` - The new password
     ///
     ///
     /// # Returns
     ///
-    /// * `Result<(), Error>` - Returns an error if the record update fails
+    /// * `Result<(), Error>` - Returns an error if the password change fails
     ///
     ///
     /// # Example
     ///
     /// ```no_run
-    /// use simple_ldap::{
-    ///     LdapClient, LdapConfig,
-    ///     ldap3::Mod
-    /// };
+    /// use simple_ldap::{BindMethod, LdapClient, LdapConfig, TlsMode};
     /// use url::Url;
-    /// use std::collections::HashSet;
     ///
     /// #[tokio::main]
     /// async fn main(){
     ///     let ldap_config = LdapConfig {
-    ///         bind_dn: String::from("cn=manager"),
-    ///         bind_password: String::from("password"),
+    ///         bind_dn: Some(String::from("cn=manager")),
+    ///         bind_password: Some(String::from("password")),
     ///         ldap_url: Url::parse("ldaps://localhost:1389/dc=example,dc=com").unwrap(),
+    ///         tls: TlsMode::Ldaps,
+    ///         tls_options: None,
+    ///         bind_method: BindMethod::Simple,
     ///         dn_attribute: None,
+    ///         retry: None,
+    ///         operation_timeout: None,
+    ///         password_policy: false,
     ///         connection_settings: None
     ///     };
     ///
     ///     let mut client = LdapClient::new(ldap_config).await.unwrap();
     ///
-    ///     let data = vec![
-    ///         Mod::Replace("cn", HashSet::from(["Jhon_Update"])),
-    ///         Mod::Replace("sn", HashSet::from(["Eliet_Update"])),
-    ///     ];
-    ///
-    ///     let result = client.update(
-    ///         "e219fbc0-6df5-4bc3-a6ee-986843bb157e",
-    ///         "ou=people,dc=example,dc=com",
-    ///         data,
-    ///         None
+    ///     let result = client.change_password(
+    ///         "uid=bd9b91ec-7a69-4166-bf67-cc7e553b2fd9,ou=people,dc=example,dc=com",
+    ///         Some("old secret"),
+    ///         "new secret"
     ///     ).await;
     /// }
     /// ```
-    ///
-    pub async fn update(
+    pub async fn change_password(
         &mut self,
-        uid: &str,
-        base: &str,
-        data: Vec<Mod<&str>>,
-        new_uid: Option<&str>,
+        user_dn: &str,
+        old_password: Option<&str>,
+        new_password: &str,
     ) -> Result<(), Error> {
-        let dn = format!("uid={uid},{base}");
-
-        let res = self.ldap.modify(dn.as_str(), data).await;
-        if let Err(err) = res {
-            return Err(Error::Update(
-                format!("Error updating record: {err:?}"),
-                err,
-            ));
-        }
-
-        let res = res.unwrap().success();
-        if let Err(err) = res {
-            match err {
-                LdapError::LdapResult { result } => {
-                    if result.rc == NO_SUCH_RECORD {
-                        return Err(Error::NotFound(format!(
-                            "No records found for the uid: {uid:?}"
-                        )));
-                    }
-                }
-                _ => {
-                    return Err(Error::Update(
-                        format!("Error updating record: {err:?}"),
-                        err,
-                    ));
-                }
-            }
-        }
-
-        if new_uid.is_none() {
-            return Ok(());
-        }
-
-        let new_uid = new_uid.unwrap();
-        if !uid.eq_ignore_ascii_case(new_uid) {
-            let new_dn = format!("uid={new_uid}");
-            let dn_update = self
-                .ldap
-                .modifydn(dn.as_str(), new_dn.as_str(), true, None)
-                .await;
-            if let Err(err) = dn_update {
-                error!("Failed to update dn for record {:?} error {:?}", uid, err);
-                return Err(Error::Update(
-                    format!("Failed to update dn for record {uid:?}"),
-                    err,
-                ));
-            }
-
-            let dn_update = dn_update.unwrap().success();
-            if let Err(err) = dn_update {
-                error!("Failed to update dn for record {:?} error {:?}", uid, err);
-                return Err(Error::Update(
-                    format!("Failed to update dn for record {uid:?}"),
-                    err,
-                ));
-            }
-
-            let res = dn_update.unwrap();
-            debug!("Successfully updated dn result: {:?}", res);
-        }
-
+        let password_modify = PasswordModify {
+            user_id: Some(user_dn),
+            old_pass: old_password,
+            new_pass: Some(new_password),
+        };
+        self.password_modify(password_modify).await?;
+        debug!("Successfully changed password of {user_dn:?}");
         Ok(())
     }
 
     ///
-    /// Delete a record in the LDAP server. The record will be deleted in the provided base DN.
+    /// Reset a user's password to one generated by the server.
+    /// This uses the Password Modify extended operation ([RFC 3062](https://datatracker.ietf.org/doc/html/rfc3062)).
+    ///
+    /// This is an administrative operation, so the client needs to be bound with sufficient rights.
     ///
     /// # Arguments
     ///
-    /// * `uid` - The uid of the record
-    /// * `base` - The base DN to delete the record
+    /// * `user_dn` - The dn of the user
     ///
     ///
     /// # Returns
     ///
-    /// * `Result<(), Error>` - Returns an error if the record delete fails
+    /// * `Result<String, Error>` - The new password generated by the server
     ///
     ///
     /// # Example
     ///
     /// ```no_run
-    /// use simple_ldap::{LdapClient, LdapConfig};
+    /// use simple_ldap::{BindMethod, LdapClient, LdapConfig, TlsMode};
     /// use url::Url;
     ///
     /// #[tokio::main]
     /// async fn main(){
     ///     let ldap_config = LdapConfig {
-    ///         bind_dn: String::from("cn=manager"),
-    ///         bind_password: String::from("password"),
+    ///         bind_dn: Some(String::from("cn=manager")),
+    ///         bind_password: Some(String::from("password")),
     ///         ldap_url: Url::parse("ldaps://localhost:1389/dc=example,dc=com").unwrap(),
+    ///         tls: TlsMode::Ldaps,
+    ///         tls_options: None,
+    ///         bind_method: BindMethod::Simple,
     ///         dn_attribute: None,
+    ///         retry: None,
+    ///         operation_timeout: None,
+    ///         password_policy: false,
     ///         connection_settings: None
     ///     };
     ///
     ///     let mut client = LdapClient::new(ldap_config).await.unwrap();
     ///
-    ///     let result = client.delete("e219fbc0-6df5-4bc3-a6ee-986843bb157e", "ou=people,dc=example,dc=com").await;
+    ///     let generated_password = client.reset_password(
+    ///         "uid=bd9b91ec-7a69-4166-bf67-cc7e553b2fd9,ou=people,dc=example,dc=com"
+    ///     ).await;
     /// }
     /// ```
-    pub async fn delete(&mut self, uid: &str, base: &str) -> Result<(), Error> {
-        let dn = format!("uid={uid},{base}");
-        let delete = self.ldap.delete(dn.as_str()).await;
+    pub async fn reset_password(&mut self, user_dn: &str) -> Result<String, Error> {
+        let password_modify = PasswordModify {
+            user_id: Some(user_dn),
+            old_pass: None,
+            // Leaving this out asks the server to generate one.
+            new_pass: None,
+        };
+        let exop = self.password_modify(password_modify).await?;
+
+        let gen_pass = exop
+            .val
+            .as_deref()
+            .and_then(parse_generated_password)
+            .ok_or_else(|| {
+                Error::Mapping(
+                    format!("The server didn't return a generated password for {user_dn:?}"),
+                    None,
+                )
+            })?;
 
-        if let Err(err) = delete {
-            return Err(Error::Delete(
-                format!("Error deleting record: {err:?}"),
-                err,
-            ));
-        }
-        let delete = delete.unwrap().success();
-        if let Err(err) = delete {
-            match err {
-                LdapError::LdapResult { result } => {
-                    if result.rc == NO_SUCH_RECORD {
-                        return Err(Error::NotFound(format!(
-                            "No records found for the uid: {uid:?}"
-                        )));
-                    }
-                }
-                _ => {
-                    return Err(Error::Delete(
-                        format!("Error deleting record: {err:?}"),
-                        err,
-                    ));
-                }
+        debug!("Successfully reset password of {user_dn:?}");
+        Ok(gen_pass)
+    }
+
+    async fn password_modify(
+        &mut self,
+        password_modify: PasswordModify<'_>,
+    ) -> Result<Exop, Error> {
+        let user_dn = password_modify.user_id.unwrap_or_default().to_owned();
+
+        self.reconnecting(async |mut ldap| {
+            let res = ldap.extended(password_modify).await;
+            let res = check_timeout(&mut ldap, res).await?.map_err(|err| {
+                Error::Extended(
+                    format!("Error modifying password of {user_dn:?}"),
+                    Box::new(err),
+                )
+            })?;
+
+            match res.success() {
+                Ok((exop, _)) => Ok(exop),
+                Err(LdapError::LdapResult { result }) if result.rc == NO_SUCH_RECORD => Err(
+                    Error::NotFound(format!("No records found for the dn: {user_dn:?}")),
+                ),
+                Err(err) => Err(Error::Extended(
+                    format!("Error modifying password of {user_dn:?}"),
+                    Box::new(err),
+                )),
             }
-        }
-        debug!("Successfully deleted record result: {:?}", uid);
-        Ok(())
+        })
+        .await
     }
 
     ///
@@ -1028,16 +3235,22 @@ impl LdapClient {
     /// # Example
     ///
     /// ```no_run
-    /// use simple_ldap::{LdapClient, LdapConfig};
+    /// use simple_ldap::{BindMethod, LdapClient, LdapConfig, TlsMode};
     /// use url::Url;
     ///
     /// #[tokio::main]
     /// async fn main(){
     ///     let ldap_config = LdapConfig {
-    ///         bind_dn: String::from("cn=manager"),
-    ///         bind_password: String::from("password"),
+    ///         bind_dn: Some(String::from("cn=manager")),
+    ///         bind_password: Some(String::from("password")),
     ///         ldap_url: Url::parse("ldaps://localhost:1389/dc=example,dc=com").unwrap(),
+    ///         tls: TlsMode::Ldaps,
+    ///         tls_options: None,
+    ///         bind_method: BindMethod::Simple,
     ///         dn_attribute: None,
+    ///         retry: None,
+    ///         operation_timeout: None,
+    ///         password_policy: false,
     ///         connection_settings: None
     ///     };
     ///
@@ -1060,18 +3273,28 @@ impl LdapClient {
             ("ou", HashSet::from([group_ou])),
             ("description", HashSet::from([description])),
         ];
-        let save = self.ldap.add(dn.as_str(), data).await;
-        if let Err(err) = save {
-            return Err(Error::Create(format!("Error saving record: {err:?}"), err));
-        }
-        let save = save.unwrap().success();
+        self.reconnecting(async |mut ldap| {
+            let save = ldap.add(dn.as_str(), data).await;
+            let save = check_timeout(&mut ldap, save).await?;
+            if let Err(err) = save {
+                return Err(Error::Create(
+                    format!("Error saving record: {err:?}"),
+                    Box::new(err),
+                ));
+            }
+            let save = save.unwrap().success();
 
-        if let Err(err) = save {
-            return Err(Error::Create(format!("Error creating group: {err:?}"), err));
-        }
-        let res = save.unwrap();
-        debug!("Successfully created group result: {:?}", res);
-        Ok(())
+            if let Err(err) = save {
+                return Err(Error::Create(
+                    format!("Error creating group: {err:?}"),
+                    Box::new(err),
+                ));
+            }
+            let res = save.unwrap();
+            debug!("Successfully created group result: {:?}", res);
+            Ok(())
+        })
+        .await
     }
 
     ///
@@ -1091,16 +3314,22 @@ impl LdapClient {
     /// # Example
     ///
     /// ```no_run
-    /// use simple_ldap::{LdapClient, LdapConfig};
+    /// use simple_ldap::{BindMethod, LdapClient, LdapConfig, TlsMode};
     /// use url::Url;
     ///
     /// #[tokio::main]
     /// async fn main(){
     ///     let ldap_config = LdapConfig {
-    ///         bind_dn: String::from("cn=manager"),
-    ///         bind_password: String::from("password"),
+    ///         bind_dn: Some(String::from("cn=manager")),
+    ///         bind_password: Some(String::from("password")),
     ///         ldap_url: Url::parse("ldaps://localhost:1389/dc=example,dc=com").unwrap(),
+    ///         tls: TlsMode::Ldaps,
+    ///         tls_options: None,
+    ///         bind_method: BindMethod::Simple,
     ///         dn_attribute: None,
+    ///         retry: None,
+    ///         operation_timeout: None,
+    ///         password_policy: false,
     ///         connection_settings: None
     ///     };
     ///
@@ -1119,33 +3348,37 @@ impl LdapClient {
         let mut mods = Vec::new();
         let users = users.iter().copied().collect::<HashSet<&str>>();
         mods.push(Mod::Replace("member", users));
-        let res = self.ldap.modify(group_dn, mods).await;
-        if let Err(err) = res {
-            return Err(Error::Update(
-                format!("Error updating record: {err:?}"),
-                err,
-            ));
-        }
+        self.reconnecting(async |mut ldap| {
+            let res = ldap.modify(group_dn, mods).await;
+            let res = check_timeout(&mut ldap, res).await?;
+            if let Err(err) = res {
+                return Err(Error::Update(
+                    format!("Error updating record: {err:?}"),
+                    Box::new(err),
+                ));
+            }
 
-        let res = res.unwrap().success();
-        if let Err(err) = res {
-            match err {
-                LdapError::LdapResult { result } => {
-                    if result.rc == NO_SUCH_RECORD {
-                        return Err(Error::NotFound(format!(
-                            "No records found for the uid: {group_dn:?}"
-                        )));
+            let res = res.unwrap().success();
+            if let Err(err) = res {
+                match err {
+                    LdapError::LdapResult { result } => {
+                        if result.rc == NO_SUCH_RECORD {
+                            return Err(Error::NotFound(format!(
+                                "No records found for the uid: {group_dn:?}"
+                            )));
+                        }
+                    }
+                    _ => {
+                        return Err(Error::Update(
+                            format!("Error updating record: {err:?}"),
+                            Box::new(err),
+                        ));
                     }
-                }
-                _ => {
-                    return Err(Error::Update(
-                        format!("Error updating record: {err:?}"),
-                        err,
-                    ));
                 }
             }
-        }
-        Ok(())
+            Ok(())
+        })
+        .await
     }
 
     ///
@@ -1157,18 +3390,23 @@ impl LdapClient {
     /// * `base_dn` - The base dn to search for the users
     /// * `scope` - The scope of the search
     /// * `attributes` - The attributes to return from the search
+    /// * `mapping_errors` - Whether to fail or to collect the errors of members that can't be deserialized.
     ///
     ///
     /// # Returns
     ///
-    /// * `Result<Vec<T>, Error>` - Returns a vector of structs of type T
+    /// * `Result<Entries<T>, Error>` - The members as structs of type T, and with [`MappingErrors::Collect`]
+    ///   the errors of the skipped ones.
+    ///   If a size or time limit cut the search of the members short, the members found before it
+    ///   are returned with [`Entries::limit`] telling which limit was hit.
+    ///   Other failures fail the whole search, like in [`search_all`](Self::search_all).
     ///
     ///
     /// # Example
     ///
     /// ```no_run
     /// use simple_ldap::{
-    ///     LdapClient, LdapConfig,
+    ///     BindMethod, LdapClient, LdapConfig, MappingErrors, TlsMode,
     ///     ldap3::Scope
     /// };
     /// use url::Url;
@@ -1184,10 +3422,16 @@ impl LdapClient {
     /// #[tokio::main]
     /// async fn main(){
     ///     let ldap_config = LdapConfig {
-    ///         bind_dn: String::from("cn=manager"),
-    ///         bind_password: String::from("password"),
+    ///         bind_dn: Some(String::from("cn=manager")),
+    ///         bind_password: Some(String::from("password")),
     ///         ldap_url: Url::parse("ldaps://localhost:1389/dc=example,dc=com").unwrap(),
+    ///         tls: TlsMode::Ldaps,
+    ///         tls_options: None,
+    ///         bind_method: BindMethod::Simple,
     ///         dn_attribute: None,
+    ///         retry: None,
+    ///         operation_timeout: None,
+    ///         password_policy: false,
     ///         connection_settings: None
     ///     };
     ///
@@ -1197,9 +3441,11 @@ impl LdapClient {
     ///         "cn=test_group,ou=groups,dc=example,dc=com",
     ///         "ou=people,dc=example,dc=com",
     ///         Scope::OneLevel,
-    ///         vec!["cn", "sn", "uid"]
+    ///         vec!["cn", "sn", "uid"],
+    ///         MappingErrors::Fail,
     ///     ).await
-    ///     .unwrap();
+    ///     .unwrap()
+    ///     .entries;
     /// }
     /// ```
     ///
@@ -1209,33 +3455,34 @@ impl LdapClient {
         base_dn: &str,
         scope: Scope,
         attributes: A,
-    ) -> Result<Vec<T>, Error>
+        mapping_errors: MappingErrors,
+    ) -> Result<Entries<T>, Error>
     where
         A: AsRef<[S]> + Send + Sync + Clone + fmt::Debug + 'a,
         S: AsRef<str> + Send + Sync + Clone + fmt::Debug + 'a,
         T: for<'de> serde::Deserialize<'de>,
     {
-        let search = self
-            .ldap
-            .search(
-                group_dn,
-                Scope::Base,
-                "(objectClass=groupOfNames)",
-                vec!["member"],
-            )
-            .await;
+        let mut ldap = self.operation_handle();
+        let search = search_entries(
+            &mut ldap,
+            group_dn,
+            Scope::Base,
+            "(objectClass=groupOfNames)",
+            vec!["member"],
+        )
+        .await?;
 
         if let Err(error) = search {
             return Err(Error::Query(
                 format!("Error searching for record: {error:?}"),
-                error,
+                Box::new(error),
             ));
         }
         let result = search.unwrap().success();
         if let Err(error) = result {
             return Err(Error::Query(
                 format!("Error searching for record: {error:?}"),
-                error,
+                Box::new(error),
             ));
         }
 
@@ -1265,44 +3512,25 @@ impl LdapClient {
             .map(|(arrta, value)| (arrta.to_owned(), value.to_owned()))
             .filter(|(attra, _)| attra.eq("member"))
             .flat_map(|(_, value)| value)
-            .map(|val| {
-                val.split(',').collect::<Vec<&str>>()[0]
-                    .split('=')
-                    .map(|split| split.to_string())
-                    .collect::<Vec<String>>()
-            })
-            .map(|uid| EqFilter::from(uid[0].to_string(), uid[1].to_string()))
-            .for_each(|eq| or_filter.add(Box::new(eq)));
-
-        let result = self
-            .streaming_search(base_dn, scope, &or_filter, attributes, None, Vec::new())
-            .await;
-
-        let mut members = Vec::new();
-        match result {
-            Ok(result) => {
-                let mut stream = Box::pin(result);
-                while let Some(member) = stream.next().await {
-                    match member {
-                        Ok(member) => {
-                            let user: T = member.to_record().unwrap();
-                            members.push(user);
-                        }
-                        Err(err) => {
-                            // TODO: Exit with an error instead?
-                            error!("Error getting member error {:?}", err);
-                        }
-                    }
-                }
-                return Ok(members);
-            }
-            Err(err) => {
-                // TODO: Exit with an error instead?
-                error!("Error getting members {:?} error {:?}", group_dn, err);
-            }
-        }
+            .map(|val| {
+                val.split(',').collect::<Vec<&str>>()[0]
+                    .split('=')
+                    .map(|split| split.to_string())
+                    .collect::<Vec<String>>()
+            })
+            .map(|uid| EqFilter::from(uid[0].to_string(), uid[1].to_string()))
+            .for_each(|eq| or_filter.add(Box::new(eq)));
 
-        Ok(members)
+        self.search_all(
+            base_dn,
+            scope,
+            &or_filter,
+            attributes,
+            None,
+            Vec::new(),
+            mapping_errors,
+        )
+        .await
     }
 
     ///
@@ -1324,17 +3552,23 @@ impl LdapClient {
     /// # Example
     ///
     /// ```no_run
-    /// use simple_ldap::{LdapClient, LdapConfig};
+    /// use simple_ldap::{BindMethod, LdapClient, LdapConfig, TlsMode};
     /// use url::Url;
     /// use std::collections::HashSet;
     ///
     /// #[tokio::main]
     /// async fn main(){
     ///     let ldap_config = LdapConfig {
-    ///         bind_dn: String::from("cn=manager"),
-    ///         bind_password: String::from("password"),
+    ///         bind_dn: Some(String::from("cn=manager")),
+    ///         bind_password: Some(String::from("password")),
     ///         ldap_url: Url::parse("ldaps://localhost:1389/dc=example,dc=com").unwrap(),
+    ///         tls: TlsMode::Ldaps,
+    ///         tls_options: None,
+    ///         bind_method: BindMethod::Simple,
     ///         dn_attribute: None,
+    ///         retry: None,
+    ///         operation_timeout: None,
+    ///         password_policy: false,
     ///         connection_settings: None
     ///     };
     ///
@@ -1352,33 +3586,37 @@ impl LdapClient {
         let mut mods = Vec::new();
         let users = users.iter().copied().collect::<HashSet<&str>>();
         mods.push(Mod::Delete("member", users));
-        let res = self.ldap.modify(group_dn, mods).await;
-        if let Err(err) = res {
-            return Err(Error::Update(
-                format!("Error removing users from group:{group_dn:?}: {err:?}"),
-                err,
-            ));
-        }
+        self.reconnecting(async |mut ldap| {
+            let res = ldap.modify(group_dn, mods).await;
+            let res = check_timeout(&mut ldap, res).await?;
+            if let Err(err) = res {
+                return Err(Error::Update(
+                    format!("Error removing users from group:{group_dn:?}: {err:?}"),
+                    Box::new(err),
+                ));
+            }
 
-        let res = res.unwrap().success();
-        if let Err(err) = res {
-            match err {
-                LdapError::LdapResult { result } => {
-                    if result.rc == NO_SUCH_RECORD {
-                        return Err(Error::NotFound(format!(
-                            "No records found for the uid: {group_dn:?}"
-                        )));
+            let res = res.unwrap().success();
+            if let Err(err) = res {
+                match err {
+                    LdapError::LdapResult { result } => {
+                        if result.rc == NO_SUCH_RECORD {
+                            return Err(Error::NotFound(format!(
+                                "No records found for the uid: {group_dn:?}"
+                            )));
+                        }
+                    }
+                    _ => {
+                        return Err(Error::Update(
+                            format!("Error removing users from group:{group_dn:?}: {err:?}"),
+                            Box::new(err),
+                        ));
                     }
-                }
-                _ => {
-                    return Err(Error::Update(
-                        format!("Error removing users from group:{group_dn:?}: {err:?}"),
-                        err,
-                    ));
                 }
             }
-        }
-        Ok(())
+            Ok(())
+        })
+        .await
     }
 
     ///
@@ -1392,22 +3630,30 @@ impl LdapClient {
     ///
     /// # Returns
     ///
-    /// * `Result<Vec<String>, Error>` - Returns a vector of group names. Will be empty when there are no associated groups
+    /// * `Result<Entries<String>, Error>` - The group names. Will be empty when there are no associated groups.
+    ///   If a size or time limit cut the search short, the groups found before it
+    ///   are returned with [`Entries::limit`] telling which limit was hit.
     ///
     ///
     /// # Example
     ///
     /// ```no_run
-    /// use simple_ldap::{GroupObjectClass, LdapClient, LdapConfig};
+    /// use simple_ldap::{BindMethod, GroupObjectClass, LdapClient, LdapConfig, TlsMode};
     /// use url::Url;
     ///
     /// #[tokio::main]
     /// async fn main(){
     ///     let ldap_config = LdapConfig {
-    ///         bind_dn: String::from("cn=manager"),
-    ///         bind_password: String::from("password"),
+    ///         bind_dn: Some(String::from("cn=manager")),
+    ///         bind_password: Some(String::from("password")),
     ///         ldap_url: Url::parse("ldaps://localhost:1389/dc=example,dc=com").unwrap(),
+    ///         tls: TlsMode::Ldaps,
+    ///         tls_options: None,
+    ///         bind_method: BindMethod::Simple,
     ///         dn_attribute: None,
+    ///         retry: None,
+    ///         operation_timeout: None,
+    ///         password_policy: false,
     ///         connection_settings: None
     ///     };
     ///
@@ -1423,7 +3669,7 @@ impl LdapClient {
         group_ou: &str,
         user_dn: &str,
         group_object_class: GroupObjectClass,
-    ) -> Result<Vec<String>, Error> {
+    ) -> Result<Entries<String>, Error> {
         let group_filter = Box::new(EqFilter::from(
             "objectClass".to_string(),
             group_object_class.to_string(),
@@ -1434,37 +3680,27 @@ impl LdapClient {
         filter.add(group_filter);
         filter.add(user_filter);
 
-        let search = self
-            .ldap
-            .search(
-                group_ou,
-                Scope::Subtree,
-                filter.filter().as_str(),
-                vec!["cn"],
-            )
-            .await;
+        let mut ldap = self.search_handle();
+        let search = search_entries(
+            &mut ldap,
+            group_ou,
+            Scope::Subtree,
+            filter.filter().as_str(),
+            vec!["cn"],
+        )
+        .await?;
 
         if let Err(error) = search {
-            return Err(Error::Query(
-                format!("Error searching for record: {error:?}"),
-                error,
-            ));
-        }
-        let result = search.unwrap().success();
-        if let Err(error) = result {
-            return Err(Error::Query(
+            return Err(search_error(
                 format!("Error searching for record: {error:?}"),
                 error,
             ));
         }
+        let (records, limit) = partial_success(search.unwrap()).map_err(|error| {
+            search_error(format!("Error searching for record: {error:?}"), error)
+        })?;
 
-        let records = result.unwrap().0;
-
-        if records.is_empty() {
-            return Ok(Vec::new());
-        }
-
-        let record = records
+        let groups = records
             .iter()
             .map(|record| SearchEntry::construct(record.to_owned()))
             .map(|se| se.attrs)
@@ -1477,7 +3713,11 @@ impl LdapClient {
             })
             .collect::<Vec<String>>();
 
-        Ok(record)
+        Ok(Entries {
+            entries: groups,
+            errors: Vec::new(),
+            limit,
+        })
     }
 
     ///
@@ -1490,22 +3730,28 @@ impl LdapClient {
     ///
     /// # Returns
     ///
-    /// * `Result<Vec<String>, Error>` - Returns a vector of group names
+    /// * `Result<Entries<String>, Error>` - The group names, as with [`get_associated_groups`](Self::get_associated_groups).
     ///
     ///
     /// # Example
     ///
     /// ```no_run
-    /// use simple_ldap::{LdapClient, LdapConfig};
+    /// use simple_ldap::{BindMethod, LdapClient, LdapConfig, TlsMode};
     /// use url::Url;
     ///
     /// #[tokio::main]
     /// async fn main(){
     ///     let ldap_config = LdapConfig {
-    ///         bind_dn: String::from("cn=manager"),
-    ///         bind_password: String::from("password"),
+    ///         bind_dn: Some(String::from("cn=manager")),
+    ///         bind_password: Some(String::from("password")),
     ///         ldap_url: Url::parse("ldaps://localhost:1389/dc=example,dc=com").unwrap(),
+    ///         tls: TlsMode::Ldaps,
+    ///         tls_options: None,
+    ///         bind_method: BindMethod::Simple,
     ///         dn_attribute: None,
+    ///         retry: None,
+    ///         operation_timeout: None,
+    ///         password_policy: false,
     ///         connection_settings: None
     ///     };
     ///
@@ -1523,19 +3769,431 @@ impl LdapClient {
         &mut self,
         group_ou: &str,
         user_dn: &str,
-    ) -> Result<Vec<String>, Error> {
+    ) -> Result<Entries<String>, Error> {
         match self
             .get_associated_groups(group_ou, user_dn, GroupObjectClass::default())
             .await
         {
-            Ok(v) if v.is_empty() => Err(Error::NotFound(String::from(
-                "User does not belong to any groups",
-            ))),
+            Ok(groups) if groups.entries.is_empty() && groups.limit.is_none() => Err(
+                Error::NotFound(String::from("User does not belong to any groups")),
+            ),
             r => r,
         }
     }
 }
 
+/// Turn a delete response into the result we report to the user.
+fn delete_result_to_error(result: LdapResult, dn: &str) -> Result<(), Error> {
+    match result.success() {
+        Ok(_) => Ok(()),
+        Err(LdapError::LdapResult { result }) if result.rc == NO_SUCH_RECORD => Err(
+            Error::NotFound(format!("No records found for the dn: {dn:?}")),
+        ),
+        Err(err) => Err(Error::Delete(
+            format!("Error deleting record {dn:?}"),
+            Box::new(err),
+        )),
+    }
+}
+
+/// Open a new connection according to the config.
+///
+/// This doesn't bind.
+async fn connect(config: &LdapConfig) -> Result<Ldap, Error> {
+    let settings = config.connection_settings.clone().unwrap_or_default();
+
+    let scheme = config.ldap_url.scheme();
+    let settings = match (config.tls, scheme) {
+        (TlsMode::None, _) if config.tls_options.is_some() => {
+            return Err(Error::Config(String::from(
+                "TLS options were given, but TLS mode is None",
+            )));
+        }
+        (TlsMode::None, "ldap" | "ldapi") => settings,
+        #[cfg(any(feature = "tls-native", feature = "tls-rustls"))]
+        (TlsMode::Ldaps, "ldaps") => settings,
+        #[cfg(any(feature = "tls-native", feature = "tls-rustls"))]
+        (TlsMode::StartTls, "ldap") => settings.set_starttls(true),
+        #[cfg(not(any(feature = "tls-native", feature = "tls-rustls")))]
+        (TlsMode::Ldaps | TlsMode::StartTls, _) => {
+            return Err(Error::Config(format!(
+                "TLS mode {:?} requires the tls-native or tls-rustls feature",
+                config.tls
+            )));
+        }
+        (tls, scheme) => {
+            return Err(Error::Config(format!(
+                "TLS mode {tls:?} doesn't agree with the URL scheme {scheme:?}"
+            )));
+        }
+    };
+
+    let settings = match &config.tls_options {
+        Some(options) => options.apply(settings)?,
+        None => settings,
+    };
+
+    // With StartTLS, the upgrade happens here before returning the connection.
+    // A failed negotiation fails the whole connection.
+    let (conn, mut ldap) = LdapConnAsync::from_url_with_settings(settings, &config.ldap_url)
+        .await
+        .map_err(|ldap_err| {
+            Error::Connection(
+                String::from("Failed to initialize LDAP connection."),
+                Box::new(ldap_err),
+            )
+        })?;
+
+    ldap3::drive!(conn);
+
+    // Must happen before the credentials are sent in the bind.
+    if let Some(options) = &config.tls_options {
+        options.verify_pinned_certificate(&mut ldap).await?;
+    }
+
+    Ok(ldap)
+}
+
+/// Abandon the last operation of the handle if it timed out.
+///
+/// The timeout is reported as `Error::Timeout`.
+/// Other results are passed on for the usual handling.
+async fn check_timeout<T>(
+    ldap: &mut Ldap,
+    result: Result<T, LdapError>,
+) -> Result<Result<T, LdapError>, Error> {
+    match result {
+        Err(LdapError::Timeout { elapsed }) => Err(abandon_timed_out(ldap, elapsed).await),
+        result => Ok(result),
+    }
+}
+
+/// Abandon the last operation of the handle.
+///
+/// ldap3 stops waiting for the response of a timed out operation,
+/// but without this the server would carry on with it.
+async fn abandon_timed_out(ldap: &mut Ldap, elapsed: Elapsed) -> Error {
+    let msgid = ldap.last_id();
+    if let Err(abandon_err) = ldap.abandon(msgid).await {
+        warn!("Failed to abandon the timed out operation {msgid}: {abandon_err}");
+    }
+
+    Error::Timeout(format!("Operation timed out: {elapsed}"))
+}
+
+/// Search for all the entries at once, like `Ldap::search`, but abandon the search if it times out.
+///
+/// `Ldap::search` runs the search on an internal handle, losing the message id needed for abandoning.
+/// The result is the same as with [`check_timeout`].
+async fn search_entries<'a, S, A>(
+    ldap: &mut Ldap,
+    base: &str,
+    scope: Scope,
+    filter: &str,
+    attributes: A,
+) -> Result<Result<SearchResult, LdapError>, Error>
+where
+    S: AsRef<str> + Send + Sync + 'a,
+    A: AsRef<[S]> + Send + Sync + 'a,
+{
+    let mut stream = match ldap
+        .streaming_search_with(EntriesOnly::new(), base, scope, filter, attributes)
+        .await
+    {
+        Ok(stream) => stream,
+        // Nothing to abandon, the search wasn't even sent.
+        Err(LdapError::Timeout { elapsed }) => {
+            return Err(Error::Timeout(format!("Search timed out: {elapsed}")));
+        }
+        Err(error) => return Ok(Err(error)),
+    };
+
+    let mut entries = Vec::new();
+    loop {
+        match stream.next().await {
+            Ok(Some(entry)) => entries.push(entry),
+            Ok(None) => break,
+            Err(error) => return check_timeout(stream.ldap_handle(), Err(error)).await,
+        }
+    }
+    let result = stream.finish().await;
+
+    Ok(Ok(SearchResult(entries, result)))
+}
+
+/// Run a search with the adapters and collect all of its entries, abandoning it if it times out.
+///
+/// Unlike [`search_entries`], the final result is left for the caller to check,
+/// so that its controls can be looked at first.
+async fn collect_search<'a, S, A>(
+    ldap: &mut Ldap,
+    adapters: Vec<Box<dyn Adapter<'a, S, A> + 'a>>,
+    base: &str,
+    scope: Scope,
+    filter: &str,
+    attributes: A,
+) -> Result<(Vec<ResultEntry>, LdapResult), Error>
+where
+    S: AsRef<str> + Send + Sync + 'a,
+    A: AsRef<[S]> + Send + Sync + 'a,
+{
+    let mut stream = ldap
+        .streaming_search_with(adapters, base, scope, filter, attributes)
+        .await
+        .map_err(start_search_error)?;
+
+    let mut entries = Vec::new();
+    loop {
+        match stream.next().await {
+            Ok(Some(entry)) => entries.push(entry),
+            Ok(None) => break,
+            Err(LdapError::Timeout { elapsed }) => {
+                return Err(abandon_timed_out(stream.ldap_handle(), elapsed).await);
+            }
+            Err(error) => return Err(start_search_error(error)),
+        }
+    }
+    let result = stream.finish().await;
+
+    Ok((entries, result))
+}
+
+/// The adapters for paging and sorting a streaming search, as needed.
+fn search_adapters<'a, S, A>(
+    page_size: Option<NonZeroU16>,
+    sort_by: Vec<SortBy>,
+) -> Result<Vec<Box<dyn Adapter<'a, S, A>>>, Error>
+where
+    // PagedResults requires Clone and Debug too.
+    A: AsRef<[S]> + Send + Sync + Clone + fmt::Debug + 'a,
+    S: AsRef<str> + Send + Sync + Clone + fmt::Debug + 'a,
+{
+    // Define the needed adapters.
+
+    // No `EntriesOnly` here, as it would merge the continuation references into one.
+    // The native stream skips the intermediate responses itself.
+    let paging_adapter: Option<Box<dyn Adapter<'a, S, A>>> =
+        page_size.map(|non_zero| Box::new(PagedResults::new(non_zero.get().into())) as _);
+
+    // Empty vec just means that we won't use the search adapter.
+    let sort_adapter: Option<Box<dyn Adapter<'a, S, A>>> = vec_to_option(sort_by)
+        .map(ServerSideSort::new)
+        .transpose()
+        .map_err(|duplicate_args_err| Error::Sort(duplicate_args_err.to_string()))?
+        .map(|adapter| Box::new(adapter) as _);
+
+    let maybe_adapters: Vec<Option<Box<dyn Adapter<'a, S, A>>>> = vec![
+        // Sort needs to be before paging, so that it's control will be included in all the page requests.
+        sort_adapter,
+        paging_adapter,
+    ];
+
+    // This might end up as no adapters but that's perfectly fine too.
+    // Internally the non adapted streaming search would anyway just call the same thing with an empty adapter list.
+    Ok(maybe_adapters.into_iter().flatten().collect())
+}
+
+/// The error for failing to start a streaming search.
+fn start_search_error(ldap_error: LdapError) -> Error {
+    Error::Query(
+        format!("Error searching for record: {ldap_error:?}"),
+        Box::new(ldap_error),
+    )
+}
+
+/// Check the result of a search, accepting a size or time limit as a partial success.
+///
+/// Returns the entries along with the limit that cut the search short, if any.
+fn partial_success(
+    search: SearchResult,
+) -> Result<(Vec<ResultEntry>, Option<SearchLimit>), LdapError> {
+    let SearchResult(entries, result) = search;
+    match SearchLimit::from_result(&result) {
+        Some(limit) => Ok((entries, Some(limit))),
+        None => result.success().map(|_| (entries, None)),
+    }
+}
+
+/// The error for a failed search.
+///
+/// Searches cut short by a limit are told apart from other failures.
+fn search_error(message: String, error: LdapError) -> Error {
+    match &error {
+        LdapError::LdapResult { result } if result.rc == u32::from(ResultCode::Referral) => {
+            referral::referral_error(result.refs.clone())
+        }
+        LdapError::LdapResult { result } => match SearchLimit::from_result(result) {
+            Some(limit) => Error::LimitExceeded(
+                format!("Search stopped by the {limit:?} limit: {}", result.text),
+                limit,
+            ),
+            None => Error::Query(message, Box::new(error)),
+        },
+        _ => Error::Query(message, Box::new(error)),
+    }
+}
+
+/// Open a new connection and bind it, as the config says.
+async fn connect_and_bind(
+    config: &LdapConfig,
+) -> Result<(Ldap, BindIdentity, PasswordPolicy), Error> {
+    let mut ldap = connect(config).await?;
+    let (bound_identity, password_policy) = bind(&mut ldap, config).await?;
+
+    Ok((ldap, bound_identity, password_policy))
+}
+
+/// Make sure the credentials fit the bind method.
+///
+/// Better to fail than to silently connect as someone else than intended.
+fn check_credentials(config: &LdapConfig) -> Result<(), Error> {
+    match (
+        config.bind_method,
+        config.bind_dn.as_deref(),
+        config.bind_password.as_deref(),
+    ) {
+        (BindMethod::Simple, Some(""), _) => Err(Error::Config(String::from(
+            "Empty bind_dn with simple bind. Use BindMethod::Anonymous for anonymous access",
+        ))),
+        (BindMethod::Simple, Some(_), Some("")) => Err(Error::Config(String::from(
+            "Empty bind_password with simple bind. That would be an unauthenticated bind",
+        ))),
+        (BindMethod::Simple, Some(_), Some(_)) => Ok(()),
+        (BindMethod::Simple, _, _) => Err(Error::Config(String::from(
+            "Simple bind requires both bind_dn and bind_password",
+        ))),
+        (_, None, None) => Ok(()),
+        (method, _, _) => Err(Error::Config(format!(
+            "bind_dn and bind_password aren't used with {method:?} bind"
+        ))),
+    }
+}
+
+/// Authenticate the connection the way the config says.
+async fn bind(
+    ldap: &mut Ldap,
+    config: &LdapConfig,
+) -> Result<(BindIdentity, PasswordPolicy), Error> {
+    // No need to abandon a timed out bind, the connection is unusable anyway.
+    if let Some(timeout) = config.operation_timeout {
+        ldap.with_timeout(timeout);
+    }
+
+    let bind_result = match config.bind_method {
+        BindMethod::Anonymous => {
+            return Ok((BindIdentity::Anonymous, PasswordPolicy::default()));
+        }
+        // The presence of these was checked in `check_credentials`.
+        BindMethod::Simple => ldap
+            .with_controls(if config.password_policy {
+                vec![PasswordPolicyRequest.into()]
+            } else {
+                Vec::new()
+            })
+            .simple_bind(
+                config.bind_dn.as_deref().unwrap_or_default(),
+                config.bind_password.as_deref().unwrap_or_default(),
+            )
+            .await
+            .and_then(LdapResult::success),
+        BindMethod::SaslExternal => ldap
+            .sasl_external_bind()
+            .await
+            .and_then(LdapResult::success),
+    };
+
+    let bind_result = bind_result
+        .map_err(|ldap_err| Error::Connection(String::from("Bind failed"), Box::new(ldap_err)))?;
+
+    let password_policy = ppolicy::password_policy(&bind_result.ctrls);
+    if password_policy != PasswordPolicy::default() {
+        warn!("Password policy of the bind: {password_policy:?}");
+    }
+
+    let bound_identity = match config.bind_method {
+        BindMethod::SaslExternal => BindIdentity::SaslExternal,
+        _ => BindIdentity::simple(config.bind_dn.as_deref().unwrap_or_default()),
+    };
+
+    Ok((bound_identity, password_policy))
+}
+
+/// Parse a DN given as an argument.
+fn parse_dn(dn: &str) -> Result<SimpleDN, Error> {
+    dn.parse()
+        .map_err(|parse_err| Error::InvalidDn(format!("Unable to parse dn {dn:?}"), parse_err))
+}
+
+/// Get the generated password out of a Password Modify response value.
+///
+/// ```text
+/// PasswdModifyResponseValue ::= SEQUENCE {
+///     genPasswd       [0]     OCTET STRING OPTIONAL }
+/// ```
+///
+/// The ldap3 parser panics on anything unexpected, this returns `None` instead.
+fn parse_generated_password(val: &[u8]) -> Option<String> {
+    let (_, sequence) = parse_tag(val).ok()?;
+    let generated = sequence
+        .match_class(TagClass::Universal)?
+        .match_id(Types::Sequence as u64)?
+        .expect_constructed()?
+        .into_iter()
+        .next()?
+        .match_class(TagClass::Context)?
+        .match_id(0)?
+        .expect_primitive()?;
+
+    String::from_utf8(generated).ok()
+}
+
+/// Get the identity out of a Who am I? response.
+///
+/// The ldap3 parser panics on an identity that isn't UTF-8, this returns an error instead.
+fn parse_authzid(exop: Exop) -> Result<Option<String>, Error> {
+    // Anonymous is signaled with an empty value, which may also be left out altogether.
+    let Some(val) = exop.val.filter(|val| !val.is_empty()) else {
+        return Ok(None);
+    };
+
+    String::from_utf8(val).map(Some).map_err(|utf8_error| {
+        Error::Extended(
+            format!("The server returned an identity that isn't UTF-8: {utf8_error}"),
+            Box::new(LdapError::DecodingUTF8),
+        )
+    })
+}
+
+/// Get the cookie out of a paged results response value.
+///
+/// ```text
+/// realSearchControlValue ::= SEQUENCE {
+///         size            INTEGER (0..maxInt),
+///         cookie          OCTET STRING }
+/// ```
+///
+/// The ldap3 parser panics on anything unexpected, this returns `None` instead.
+fn parse_paging_cookie(val: &[u8]) -> Option<Vec<u8>> {
+    let (_, sequence) = parse_tag(val).ok()?;
+    let mut components = sequence
+        .match_class(TagClass::Universal)?
+        .match_id(Types::Sequence as u64)?
+        .expect_constructed()?
+        .into_iter();
+
+    // The size is just an estimate, which nobody needs.
+    components
+        .next()?
+        .match_class(TagClass::Universal)?
+        .match_id(Types::Integer as u64)?;
+
+    components
+        .next()?
+        .match_class(TagClass::Universal)?
+        .match_id(Types::OctetString as u64)?
+        .expect_primitive()
+}
+
 /// Empty vec becomes None, otherwise it gets wrapped in Some.
 fn vec_to_option<T>(vec: Vec<T>) -> Option<Vec<T>> {
     if vec.is_empty() { None } else { Some(vec) }
@@ -1595,8 +4253,9 @@ fn to_single_value<T: for<'a> Deserialize<'a>>(search_entry: SearchEntry) -> Res
 
     // DN is always returned.
     // Adding it to the serialized fields as well.
-    let dn_iter = iter::once(search_entry.dn)
-        .map(|dn| (Value::String(String::from("dn")), Value::String(dn)));
+    let dn = search_entry.dn;
+    let dn_iter =
+        iter::once(dn.clone()).map(|dn| (Value::String(String::from("dn")), Value::String(dn)));
 
     let all_fields = string_attributes
         .chain(binary_attributes)
@@ -1605,8 +4264,7 @@ fn to_single_value<T: for<'a> Deserialize<'a>>(search_entry: SearchEntry) -> Res
 
     let value = serde_value::Value::Map(all_fields);
 
-    T::deserialize(value)
-        .map_err(|err| Error::Mapping(format!("Error converting search result to object, {err:?}")))
+    T::deserialize(value).map_err(|err| mapping_error(dn, err))
 }
 
 #[instrument(level = Level::TRACE)]
@@ -1647,8 +4305,9 @@ fn to_value<T: for<'a> Deserialize<'a>>(search_entry: SearchEntry) -> Result<T,
 
     // DN is always returned.
     // Adding it to the serialized fields as well.
-    let dn_iter = iter::once(search_entry.dn)
-        .map(|dn| (Value::String(String::from("dn")), Value::String(dn)));
+    let dn = search_entry.dn;
+    let dn_iter =
+        iter::once(dn.clone()).map(|dn| (Value::String(String::from("dn")), Value::String(dn)));
 
     let all_fields = string_attributes
         .chain(binary_attributes)
@@ -1657,8 +4316,7 @@ fn to_value<T: for<'a> Deserialize<'a>>(search_entry: SearchEntry) -> Result<T,
 
     let value = serde_value::Value::Map(all_fields);
 
-    T::deserialize(value)
-        .map_err(|err| Error::Mapping(format!("Error converting search result to object, {err:?}")))
+    T::deserialize(value).map_err(|err| mapping_error(dn, err))
 }
 
 fn map_to_multi_value(attra_value: Vec<String>) -> serde_value::Value {
@@ -1688,12 +4346,19 @@ fn map_to_multi_value_bin(attra_values: Vec<Vec<u8>>) -> serde_value::Value {
 // Allowing users to debug serialization issues from the logs.
 #[instrument(level = Level::DEBUG)]
 fn to_multi_value<T: for<'a> Deserialize<'a>>(search_entry: SearchEntry) -> Result<T, Error> {
-    let value = serde_value::to_value(SerializeWrapper(search_entry)).map_err(|err| {
-        Error::Mapping(format!("Error converting search result to object, {err:?}"))
-    })?;
+    let dn = search_entry.dn.clone();
+    let value = serde_value::to_value(SerializeWrapper(search_entry))
+        .map_err(|err| mapping_error(dn.clone(), err))?;
+
+    T::deserialize(value).map_err(|err| mapping_error(dn, err))
+}
 
-    T::deserialize(value)
-        .map_err(|err| Error::Mapping(format!("Error converting search result to object, {err:?}")))
+/// The error for an entry that couldn't be mapped to a struct.
+fn mapping_error(dn: String, err: impl fmt::Debug) -> Error {
+    Error::Mapping(
+        format!("Error converting search result {dn:?} to object, {err:?}"),
+        Some(dn),
+    )
 }
 
 fn map_to_single_value(attra_value: Option<&String>) -> serde_value::Value {
@@ -1735,6 +4400,18 @@ impl Record {
         to_value(self.search_entry)
     }
 
+    /// The names of the attributes returned for the entry.
+    ///
+    /// Unlike with `to_record`, this includes the attributes without values,
+    /// as returned with [`SearchOptions::types_only`].
+    pub fn attribute_names(&self) -> impl Iterator<Item = &str> {
+        self.search_entry
+            .attrs
+            .keys()
+            .chain(self.search_entry.bin_attrs.keys())
+            .map(String::as_str)
+    }
+
     #[deprecated(
         since = "6.0.0",
         note = "Use to_record instead. This method is deprecated and will be removed in future versions."
@@ -1754,45 +4431,211 @@ pub enum StreamResult<T> {
 /// The error type for the LDAP client
 ///
 #[derive(Debug, Error)]
+// The ldap3 errors are boxed, as they would make every `Result` of the crate large.
 pub enum Error {
     /// Error occurred when performing a LDAP query
     #[error("{0}")]
-    Query(String, #[source] LdapError),
+    Query(String, #[source] Box<LdapError>),
     /// No records found for the search criteria
+    ///
+    /// Its [`result_code`](Error::result_code) is [`ResultCode::NoSuchObject`].
     #[error("{0}")]
     NotFound(String),
     /// Multiple records found for the search criteria
     #[error("{0}")]
     MultipleResults(String),
     /// Authenticating a user failed.
-    #[error("{0}")]
-    AuthenticationFailed(String),
+    ///
+    /// See [`AuthenticationFailure::reason`] for why.
+    // Boxed, as it's much bigger than the other variants.
+    #[error(transparent)]
+    AuthenticationFailed(Box<AuthenticationFailure>),
     /// Error occurred when creating a record
     #[error("{0}")]
-    Create(String, #[source] LdapError),
+    Create(String, #[source] Box<LdapError>),
     /// Error occurred when updating a record
     #[error("{0}")]
-    Update(String, #[source] LdapError),
+    Update(String, #[source] Box<LdapError>),
     /// Error occurred when deleting a record
     #[error("{0}")]
-    Delete(String, #[source] LdapError),
+    Delete(String, #[source] Box<LdapError>),
+    /// Error occurred when comparing an attribute value
+    #[error("{0}")]
+    Compare(String, #[source] Box<LdapError>),
+    /// Error occurred when performing an extended operation
+    #[error("{0}")]
+    Extended(String, #[source] Box<LdapError>),
     /// Error occurred when mapping the search result to a struct
+    ///
+    /// Carries the DN of the entry that couldn't be mapped, if the error is about one.
     #[error("{0}")]
-    Mapping(String),
+    Mapping(String, Option<String>),
     /// Error occurred while attempting to create an LDAP connection
     #[error("{0}")]
-    Connection(String, #[source] LdapError),
+    Connection(String, #[source] Box<LdapError>),
     /// Error occurred while attempting to close an LDAP connection.
     /// Includes unbind issues.
     #[error("{0}")]
-    Close(String, #[source] LdapError),
+    Close(String, #[source] Box<LdapError>),
     /// Error occurred while abandoning the search result
     #[error("{0}")]
-    Abandon(String, #[source] LdapError),
+    Abandon(String, #[source] Box<LdapError>),
 
     /// Something wrong with Server Side Sort
     #[error("{0}")]
     Sort(String),
+
+    /// Something wrong with Virtual List View
+    #[error("{0}")]
+    VirtualListView(String),
+
+    /// A size or time limit stopped the search before it was complete.
+    ///
+    /// This is a partial success, the entries returned before this are valid.
+    #[error("{0}")]
+    LimitExceeded(String, SearchLimit),
+
+    /// The search, or a part of it, is in other servers.
+    ///
+    /// Carries the URLs of the servers. See [`ReferralPolicy`] for when this is returned,
+    /// and when it doesn't end a streaming search.
+    #[error("{0}")]
+    Referral(String, Vec<String>),
+
+    /// The operation didn't finish within the operation timeout.
+    ///
+    /// It has been abandoned on the server.
+    #[error("{0}")]
+    Timeout(String),
+
+    /// The client configuration is invalid or contradictory.
+    #[error("{0}")]
+    Config(String),
+
+    /// The server's certificate wasn't acceptable.
+    #[error("{0}")]
+    Tls(String),
+
+    /// A DN argument wasn't something we could handle.
+    #[error("{0}")]
+    InvalidDn(String, #[source] SimpleDnParseError),
+}
+
+/// Inspecting the server's response behind the error.
+///
+/// ```no_run
+/// # async fn example(client: &mut simple_ldap::LdapClient) {
+/// use std::collections::HashSet;
+///
+/// let data = vec![("cn", HashSet::from(["Sam"]))];
+/// match client.create("sam", "ou=people,dc=example,dc=com", data).await {
+///     Ok(()) => println!("Created"),
+///     Err(error) if error.is_already_exists() => println!("Sam is already there"),
+///     Err(error) => println!("Failed with {:?}: {error}", error.result_code()),
+/// }
+/// # }
+/// ```
+impl Error {
+    /// The underlying ldap3 error, if any.
+    fn ldap_error(&self) -> Option<&LdapError> {
+        match self {
+            Error::Query(_, ldap_error)
+            | Error::Create(_, ldap_error)
+            | Error::Update(_, ldap_error)
+            | Error::Delete(_, ldap_error)
+            | Error::Compare(_, ldap_error)
+            | Error::Extended(_, ldap_error)
+            | Error::Connection(_, ldap_error)
+            | Error::Close(_, ldap_error)
+            | Error::Abandon(_, ldap_error) => Some(ldap_error),
+            Error::AuthenticationFailed(failure) => failure.ldap_error(),
+            Error::NotFound(_)
+            | Error::MultipleResults(_)
+            | Error::Mapping(_, _)
+            | Error::Sort(_)
+            | Error::VirtualListView(_)
+            | Error::LimitExceeded(_, _)
+            | Error::Referral(_, _)
+            | Error::Timeout(_)
+            | Error::Config(_)
+            | Error::Tls(_)
+            | Error::InvalidDn(_, _) => None,
+        }
+    }
+
+    /// The result the server sent, if the error originates from one.
+    fn ldap_result(&self) -> Option<&LdapResult> {
+        match self {
+            Error::AuthenticationFailed(failure) => failure.ldap_result(),
+            _ => match self.ldap_error()? {
+                LdapError::LdapResult { result } => Some(result),
+                _ => None,
+            },
+        }
+    }
+
+    /// The LDAP result code of the failed operation.
+    ///
+    /// `None` if the error didn't come from the server's response,
+    /// e.g. a network failure or a deserialization issue.
+    /// [`Error::NotFound`] is always [`ResultCode::NoSuchObject`],
+    /// even when the server's response isn't kept or it just found nothing.
+    pub fn result_code(&self) -> Option<ResultCode> {
+        match self {
+            Error::NotFound(_) => Some(ResultCode::NoSuchObject),
+            _ => self.ldap_result().map(|result| ResultCode::from(result.rc)),
+        }
+    }
+
+    /// The `matchedDN` of the server's response, if it sent a non-empty one.
+    ///
+    /// With `noSuchObject` this tells the deepest existing ancestor of the requested entry.
+    pub fn matched_dn(&self) -> Option<&str> {
+        self.ldap_result()
+            .map(|result| result.matched.as_str())
+            .filter(|matched| !matched.is_empty())
+    }
+
+    /// The human readable `diagnosticMessage` of the server's response, if it sent a non-empty one.
+    pub fn diagnostic_message(&self) -> Option<&str> {
+        self.ldap_result()
+            .map(|result| result.text.as_str())
+            .filter(|text| !text.is_empty())
+    }
+
+    /// The entry being created (or renamed to) already exists.
+    pub fn is_already_exists(&self) -> bool {
+        self.result_code() == Some(ResultCode::EntryAlreadyExists)
+    }
+
+    /// The server was temporarily unable to serve the request.
+    /// Trying again later might succeed.
+    pub fn is_retryable(&self) -> bool {
+        self.result_code().is_some_and(ResultCode::is_retryable)
+    }
+
+    /// The server rejected the bind credentials.
+    pub fn is_invalid_credentials(&self) -> bool {
+        self.result_code() == Some(ResultCode::InvalidCredentials)
+    }
+
+    /// The connection failed, rather than the server rejecting the operation.
+    ///
+    /// E.g. the server restarted, or something in between dropped the connection.
+    /// A new connection might succeed. See [`LdapConfig::retry`] for doing that automatically.
+    pub fn is_connection_error(&self) -> bool {
+        matches!(
+            self.ldap_error(),
+            Some(
+                LdapError::Io { .. }
+                    | LdapError::OpSend { .. }
+                    | LdapError::ResultRecv { .. }
+                    | LdapError::IdScrubSend { .. }
+                    | LdapError::MiscSend { .. }
+                    | LdapError::EndOfStream
+            )
+        )
+    }
 }
 
 #[cfg(test)]
@@ -1806,6 +4649,70 @@ mod tests {
     use serde_with::serde_as;
     use uuid::Uuid;
 
+    #[test]
+    fn parse_password_modify_response() {
+        let generated = [0x30, 0x05, 0x80, 0x03, b'a', b'b', b'c'];
+        assert_eq!(parse_generated_password(&generated).as_deref(), Some("abc"));
+
+        // Nothing generated.
+        assert_eq!(parse_generated_password(&[0x30, 0x00]), None);
+        // Wrong tag.
+        assert_eq!(
+            parse_generated_password(&[0x30, 0x05, 0x81, 0x03, b'a', b'b', b'c']),
+            None
+        );
+        // Not UTF-8.
+        assert_eq!(
+            parse_generated_password(&[0x30, 0x03, 0x80, 0x01, 0xFF]),
+            None
+        );
+        assert_eq!(parse_generated_password(&[]), None);
+    }
+
+    #[test]
+    fn parse_who_am_i_response() {
+        let response = |val: Option<&[u8]>| Exop {
+            name: None,
+            val: val.map(<[u8]>::to_vec),
+        };
+
+        assert_eq!(
+            parse_authzid(response(Some(b"dn:cn=manager"))).ok(),
+            Some(Some(String::from("dn:cn=manager")))
+        );
+        // Anonymous.
+        assert_eq!(parse_authzid(response(Some(b""))).ok(), Some(None));
+        assert_eq!(parse_authzid(response(None)).ok(), Some(None));
+
+        let not_utf8 = parse_authzid(response(Some(&[b'd', b'n', b':', 0xFF])));
+        assert!(
+            matches!(not_utf8, Err(Error::Extended(_, ref source)) if matches!(**source, LdapError::DecodingUTF8)),
+            "{not_utf8:?}"
+        );
+    }
+
+    #[test]
+    fn parse_paged_results_response() {
+        let response = [0x30, 0x08, 0x02, 0x01, 0x05, 0x04, 0x03, 0x01, 0x02, 0x03];
+        assert_eq!(parse_paging_cookie(&response), Some(vec![1, 2, 3]));
+
+        // The last page.
+        assert_eq!(
+            parse_paging_cookie(&[0x30, 0x05, 0x02, 0x01, 0x00, 0x04, 0x00]),
+            Some(Vec::new())
+        );
+        // No cookie.
+        assert_eq!(parse_paging_cookie(&[0x30, 0x03, 0x02, 0x01, 0x05]), None);
+        // Wrong tag.
+        assert_eq!(
+            parse_paging_cookie(&[0x30, 0x05, 0x02, 0x01, 0x00, 0x80, 0x00]),
+            None
+        );
+        // Truncated.
+        assert_eq!(parse_paging_cookie(&[0x30, 0x08, 0x02, 0x01]), None);
+        assert_eq!(parse_paging_cookie(&[]), None);
+    }
+
     #[test]
     fn create_multi_value_test() {
         let mut map: HashMap<String, Vec<String>> = HashMap::new();
@@ -1858,6 +4765,23 @@ mod tests {
         assert_eq!(test.dn, dn);
     }
 
+    #[test]
+    fn mapping_error_has_dn() {
+        let dn = "CN=Thing,OU=Unit,DC=example,DC=org";
+
+        // key1 and key2 are missing.
+        let entry = SearchEntry {
+            dn: dn.to_string(),
+            attrs: HashMap::new(),
+            bin_attrs: HashMap::new(),
+        };
+
+        match to_value::<TestSingleValued>(entry) {
+            Err(Error::Mapping(_, Some(error_dn))) => assert_eq!(error_dn, dn),
+            other => panic!("Expected a mapping error with the DN, got {other:?}"),
+        }
+    }
+
     #[test]
     fn create_to_value_string_test() {
         let mut map: HashMap<String, Vec<String>> = HashMap::new();
@@ -2016,6 +4940,163 @@ mod tests {
         }
     }
 
+    #[test]
+    fn error_result_code() {
+        let error = Error::Create(
+            String::from("Error saving record"),
+            Box::new(LdapError::LdapResult {
+                result: LdapResult {
+                    rc: 68,
+                    matched: String::from("ou=people,dc=example,dc=com"),
+                    text: String::from("Entry already exists"),
+                    refs: Vec::new(),
+                    ctrls: Vec::new(),
+                },
+            }),
+        );
+
+        assert_eq!(error.result_code(), Some(ResultCode::EntryAlreadyExists));
+        assert_eq!(error.matched_dn(), Some("ou=people,dc=example,dc=com"));
+        assert_eq!(error.diagnostic_message(), Some("Entry already exists"));
+        assert!(error.is_already_exists());
+        assert!(!error.is_retryable());
+        assert!(!error.is_invalid_credentials());
+        assert!(!error.is_connection_error());
+    }
+
+    #[test]
+    fn error_without_result_code() {
+        let error = Error::Query(String::from("Lost it"), Box::new(LdapError::EndOfStream));
+        assert_eq!(error.result_code(), None);
+        assert_eq!(error.matched_dn(), None);
+        assert!(error.is_connection_error());
+
+        let error = Error::Mapping(String::from("Not a number"), None);
+        assert_eq!(error.result_code(), None);
+        assert!(!error.is_already_exists());
+        assert!(!error.is_connection_error());
+    }
+
+    #[test]
+    fn not_found_result_code() {
+        let error = Error::NotFound(String::from("Nothing here"));
+        assert_eq!(error.result_code(), Some(ResultCode::NoSuchObject));
+        assert_eq!(error.matched_dn(), None);
+        assert!(!error.is_already_exists());
+        assert!(!error.is_retryable());
+        assert!(!error.is_connection_error());
+    }
+
+    #[test]
+    fn render_dn_template() {
+        let template = BindTemplate::Dn(String::from("uid={user},ou=people,dc=example,dc=com"));
+
+        assert_eq!(
+            template.render("sam").unwrap(),
+            "uid=sam,ou=people,dc=example,dc=com"
+        );
+        // Can't escape the RDN value.
+        assert_eq!(
+            template.render("sam,ou=admins").unwrap(),
+            "uid=sam\\2cou\\3dadmins,ou=people,dc=example,dc=com"
+        );
+        assert!(matches!(
+            template.render(""),
+            Err(Error::AuthenticationFailed(_))
+        ));
+    }
+
+    #[test]
+    fn render_upn_template() {
+        let template = BindTemplate::Upn(String::from("{user}@corp.example"));
+
+        assert_eq!(template.render("sam").unwrap(), "sam@corp.example");
+        for username in ["sam@other.example", "OTHER\\sam", "sam\n"] {
+            assert!(
+                matches!(
+                    template.render(username),
+                    Err(Error::AuthenticationFailed(_))
+                ),
+                "{username:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn template_without_placeholder() {
+        let template = BindTemplate::Upn(String::from("sam@corp.example"));
+        assert!(matches!(template.render("sam"), Err(Error::Config(_))));
+    }
+
+    #[test]
+    fn credentials_must_fit_bind_method() {
+        let dn = Some("cn=manager");
+        let password = Some("password");
+
+        for (bind_method, bind_dn, bind_password, valid) in [
+            (BindMethod::Simple, dn, password, true),
+            (BindMethod::Simple, dn, Some(""), false),
+            (BindMethod::Simple, dn, None, false),
+            (BindMethod::Simple, Some(""), Some(""), false),
+            (BindMethod::Simple, None, None, false),
+            (BindMethod::Anonymous, None, None, true),
+            (BindMethod::Anonymous, dn, password, false),
+            (BindMethod::SaslExternal, None, None, true),
+            (BindMethod::SaslExternal, dn, None, false),
+        ] {
+            let config = LdapConfig {
+                bind_dn: bind_dn.map(String::from),
+                bind_password: bind_password.map(String::from),
+                ldap_url: Url::parse("ldap://localhost:1389").unwrap(),
+                tls: TlsMode::None,
+                tls_options: None,
+                bind_method,
+                dn_attribute: None,
+                retry: None,
+                operation_timeout: None,
+                password_policy: false,
+                connection_settings: None,
+            };
+
+            let result = check_credentials(&config);
+            assert_eq!(
+                result.is_ok(),
+                valid,
+                "{bind_method:?} {bind_dn:?} {bind_password:?}"
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn tls_mode_must_agree_with_scheme() {
+        for (url, tls) in [
+            ("ldaps://localhost:1389", TlsMode::None),
+            ("ldap://localhost:1389", TlsMode::Ldaps),
+            ("ldaps://localhost:1389", TlsMode::StartTls),
+        ] {
+            let config = LdapConfig {
+                bind_dn: Some(String::from("cn=manager")),
+                bind_password: Some(String::from("password")),
+                ldap_url: Url::parse(url).unwrap(),
+                tls,
+                tls_options: None,
+                bind_method: BindMethod::Simple,
+                dn_attribute: None,
+                retry: None,
+                operation_timeout: None,
+                password_policy: false,
+                connection_settings: None,
+            };
+
+            // The mismatch is caught before any connection attempt.
+            let result = LdapClient::new(config).await;
+            assert!(
+                matches!(result, Err(Error::Config(_))),
+                "{url} with {tls:?}"
+            );
+        }
+    }
+
     #[test]
     fn deserialize_binary_single_value_test() -> anyhow::Result<()> {
         #[derive(Deserialize)]
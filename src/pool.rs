@@ -1,5 +1,5 @@
 use deadpool::{
-    managed::{self, Metrics, RecycleResult},
+    managed::{self, Metrics, RecycleError, RecycleResult},
     managed_reexports,
 };
 /// # Pool
@@ -23,7 +23,7 @@ use deadpool::{
 ///
 /// ```no_run
 /// use simple_ldap::{
-///     LdapConfig,
+///     BindMethod, LdapConfig, TlsMode,
 ///     pool::build_connection_pool
 /// };
 /// use std::num::NonZeroUsize;
@@ -32,10 +32,16 @@ use deadpool::{
 /// #[tokio::main]
 /// async fn main() -> () {
 ///     let ldap_config = LdapConfig {
-///         bind_dn: String::from("cn=manager"),
-///         bind_password: String::from("password"),
+///         bind_dn: Some(String::from("cn=manager")),
+///         bind_password: Some(String::from("password")),
 ///         ldap_url: Url::parse("ldap://localhost:1389/dc=example,dc=com").unwrap(),
+///         tls: TlsMode::None,
+///         tls_options: None,
+///         bind_method: BindMethod::Simple,
 ///         dn_attribute: None,
+///         retry: None,
+///         operation_timeout: None,
+///         password_policy: false,
 ///         connection_settings: None
 ///     };
 ///     let pool_size = NonZeroUsize::new(10).unwrap();
@@ -60,7 +66,7 @@ use deadpool::{
 use std::num::NonZeroUsize;
 use tracing::debug;
 
-use crate::{Error, LdapClient, LdapConfig};
+use crate::{LdapClient, LdapConfig};
 
 // Export the pool types in a standard manner.
 // Check the source to see the types this exposes
@@ -95,16 +101,25 @@ impl deadpool::managed::Manager for Manager {
     async fn create(&self) -> Result<Self::Type, Self::Error> {
         debug!("Creating new connection");
         let ldap_client = LdapClient::new(self.config.clone()).await?;
+        debug!(
+            "Created connection bound as {}",
+            ldap_client.bound_identity()
+        );
         Ok(ldap_client)
     }
 
+    /// Checks that the connection is still usable.
+    ///
+    /// Dead connections are discarded, and the pool creates new ones in their place.
     async fn recycle(
         &self,
         client: &mut Self::Type,
         _metrics: &Metrics,
     ) -> RecycleResult<Self::Error> {
-        debug!("recycling connection");
-        client.unbind_ref().await?;
+        debug!("recycling connection bound as {}", client.bound_identity());
+        if client.ldap.is_closed() {
+            return Err(RecycleError::message("Connection is closed"));
+        }
         Ok(())
     }
 }
@@ -119,15 +134,3 @@ pub async fn build_connection_pool(
 
     Ok(pool)
 }
-
-impl LdapClient {
-    /// End the LDAP connection.
-    ///
-    /// This unbind by reference is needed by deadpool.
-    async fn unbind_ref(&mut self) -> Result<(), Error> {
-        match self.ldap.unbind().await {
-            Ok(_) => Ok(()),
-            Err(error) => Err(Error::Close(String::from("Failed to unbind"), error)),
-        }
-    }
-}
@@ -0,0 +1,94 @@
+//! This module implements the Password Policy control
+//! as described in [draft-behera-ldap-password-policy](https://datatracker.ietf.org/doc/html/draft-behera-ldap-password-policy-11).
+//!
+//! Sent with a bind, it makes the server tell about the state of the password,
+//! e.g. that it's about to expire. OpenLDAP and OpenDJ support it, Active Directory doesn't.
+
+use std::time::Duration;
+
+use derive_more::TryFrom;
+use ldap3::controls::{Control, RawControl};
+use tracing::warn;
+
+// Control is the low level component of the implementation.
+pub(crate) mod control;
+
+// Same for the request and the response.
+const PASSWORD_POLICY_OID: &str = "1.3.6.1.4.1.42.2.27.8.5.1";
+
+/// Password policy state reported by the server on bind.
+///
+/// All `None` if the server doesn't support the control, or had nothing to tell.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+pub struct PasswordPolicy {
+    /// The password expires in this time. Time to remind the user to change it.
+    pub time_before_expiration: Option<Duration>,
+    /// The password has expired, but the user may still log in this many times.
+    pub grace_logins_remaining: Option<u32>,
+    /// Something is wrong with the password or the account.
+    pub error: Option<PasswordPolicyError>,
+}
+
+/// Errors of the Password Policy control.
+///
+/// Some of these only concern modifying the password, so you won't get them on bind.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, TryFrom)]
+#[try_from(repr)]
+// Unnecessary big size but it matches what the parser spits out.
+#[repr(u64)]
+pub enum PasswordPolicyError {
+    PasswordExpired = 0,
+    AccountLocked = 1,
+    /// The password was reset by an administrator and has to be changed before anything else.
+    ChangeAfterReset = 2,
+    PasswordModNotAllowed = 3,
+    MustSupplyOldPassword = 4,
+    InsufficientPasswordQuality = 5,
+    PasswordTooShort = 6,
+    PasswordTooYoung = 7,
+    PasswordInHistory = 8,
+    PasswordTooLong = 9,
+}
+
+/// Find and parse the password policy response, if any.
+pub(crate) fn password_policy(controls: &[Control]) -> PasswordPolicy {
+    controls
+        .iter()
+        // Control type isn't parsed since this control is implemented outside ldap3
+        // so we're just working with the raw values.
+        .map(|Control(_, raw)| raw)
+        .find(|raw| raw.ctype == PASSWORD_POLICY_OID)
+        .and_then(|RawControl { val, .. }| {
+            let policy = val
+                .as_deref()
+                .and_then(control::PasswordPolicyResponse::parse);
+            if policy.is_none() {
+                warn!("Ignoring a malformed password policy response control.");
+            }
+            policy
+        })
+        .map(PasswordPolicy::from)
+        .unwrap_or_default()
+}
+
+impl From<control::PasswordPolicyResponse> for PasswordPolicy {
+    fn from(value: control::PasswordPolicyResponse) -> Self {
+        use control::Warning;
+
+        let (time_before_expiration, grace_logins_remaining) = match value.warning {
+            Some(Warning::TimeBeforeExpiration(seconds)) => {
+                (Some(Duration::from_secs(seconds)), None)
+            }
+            Some(Warning::GraceAuthNsRemaining(count)) => {
+                (None, Some(u32::try_from(count).unwrap_or(u32::MAX)))
+            }
+            None => (None, None),
+        };
+
+        PasswordPolicy {
+            time_before_expiration,
+            grace_logins_remaining,
+            error: value.error,
+        }
+    }
+}
@@ -0,0 +1,172 @@
+//! The low level control implementation of Password Policy.
+//!
+
+use ldap3::{
+    asn1::{TagClass, Types, parse_tag, parse_uint},
+    controls::RawControl,
+};
+
+use crate::ppolicy::{PASSWORD_POLICY_OID, PasswordPolicyError};
+
+/// Request control for Password Policy.
+///
+/// The request has no value, it just tells that we're interested.
+pub(crate) struct PasswordPolicyRequest;
+
+impl From<PasswordPolicyRequest> for RawControl {
+    fn from(_: PasswordPolicyRequest) -> Self {
+        RawControl {
+            ctype: PASSWORD_POLICY_OID.to_owned(),
+            // Servers not supporting it can just ignore it.
+            crit: false,
+            val: None,
+        }
+    }
+}
+
+/*******************************
+ *  Then the response control  *
+ *******************************/
+
+/// Response control for Password Policy.
+///
+/// ```text
+/// PasswordPolicyResponseValue ::= SEQUENCE {
+///     warning [0] CHOICE {
+///         timeBeforeExpiration [0] INTEGER (0 .. maxInt),
+///         graceAuthNsRemaining [1] INTEGER (0 .. maxInt) } OPTIONAL,
+///     error   [1] ENUMERATED { ... } OPTIONAL }
+/// ```
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) struct PasswordPolicyResponse {
+    pub warning: Option<Warning>,
+    pub error: Option<PasswordPolicyError>,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) enum Warning {
+    /// Seconds until the password expires.
+    TimeBeforeExpiration(u64),
+    /// Logins left after the password has expired.
+    GraceAuthNsRemaining(u64),
+}
+
+// Implicit tags, except for the warning which is a CHOICE and thus explicit.
+const WARNING_TAG: u64 = 0;
+const ERROR_TAG: u64 = 1;
+const TIME_BEFORE_EXPIRATION_TAG: u64 = 0;
+const GRACE_AUTHNS_REMAINING_TAG: u64 = 1;
+
+impl PasswordPolicyResponse {
+    /// Parse the control value.
+    ///
+    /// Unlike other controls, this one comes in bind responses, so rather than panicking
+    /// on a misbehaving server this returns `None`.
+    pub(crate) fn parse(val: &[u8]) -> Option<Self> {
+        let (_, sequence) = parse_tag(val).ok()?;
+        let components = sequence
+            .match_class(TagClass::Universal)?
+            .match_id(Types::Sequence as u64)?
+            .expect_constructed()?;
+
+        let mut response = PasswordPolicyResponse {
+            warning: None,
+            error: None,
+        };
+
+        for component in components {
+            if component.class != TagClass::Context {
+                return None;
+            }
+
+            match component.id {
+                WARNING_TAG => {
+                    let choice = component.expect_constructed()?.into_iter().next()?;
+                    let id = choice.id;
+                    let raw_value = choice.match_class(TagClass::Context)?.expect_primitive()?;
+                    let (_, value) = parse_uint(raw_value.as_slice()).ok()?;
+
+                    response.warning = Some(match id {
+                        TIME_BEFORE_EXPIRATION_TAG => Warning::TimeBeforeExpiration(value),
+                        GRACE_AUTHNS_REMAINING_TAG => Warning::GraceAuthNsRemaining(value),
+                        _ => return None,
+                    });
+                }
+                ERROR_TAG => {
+                    let raw_error = component.expect_primitive()?;
+                    let (_, numeric_error) = parse_uint(raw_error.as_slice()).ok()?;
+
+                    response.error = Some(PasswordPolicyError::try_from(numeric_error).ok()?);
+                }
+                _ => return None,
+            }
+        }
+
+        Some(response)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_time_before_expiration() {
+        let value = [0x30, 0x06, 0xA0, 0x04, 0x80, 0x02, 0x0E, 0x10];
+
+        assert_eq!(
+            PasswordPolicyResponse::parse(&value),
+            Some(PasswordPolicyResponse {
+                warning: Some(Warning::TimeBeforeExpiration(3600)),
+                error: None,
+            })
+        );
+    }
+
+    #[test]
+    fn parse_grace_logins_and_error() {
+        let value = [0x30, 0x08, 0xA0, 0x03, 0x81, 0x01, 0x02, 0x81, 0x01, 0x00];
+
+        assert_eq!(
+            PasswordPolicyResponse::parse(&value),
+            Some(PasswordPolicyResponse {
+                warning: Some(Warning::GraceAuthNsRemaining(2)),
+                error: Some(PasswordPolicyError::PasswordExpired),
+            })
+        );
+    }
+
+    #[test]
+    fn parse_error_only() {
+        let value = [0x30, 0x03, 0x81, 0x01, 0x01];
+
+        assert_eq!(
+            PasswordPolicyResponse::parse(&value),
+            Some(PasswordPolicyResponse {
+                warning: None,
+                error: Some(PasswordPolicyError::AccountLocked),
+            })
+        );
+    }
+
+    #[test]
+    fn parse_empty() {
+        assert_eq!(
+            PasswordPolicyResponse::parse(&[0x30, 0x00]),
+            Some(PasswordPolicyResponse {
+                warning: None,
+                error: None,
+            })
+        );
+    }
+
+    #[test]
+    fn parse_garbage() {
+        assert_eq!(PasswordPolicyResponse::parse(&[0x04, 0x01, 0x00]), None);
+        assert_eq!(
+            PasswordPolicyResponse::parse(&[0x30, 0x03, 0x81, 0x01, 0x63]),
+            None
+        );
+        assert_eq!(PasswordPolicyResponse::parse(&[]), None);
+    }
+}
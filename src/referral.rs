@@ -0,0 +1,438 @@
+//! Referrals and search continuation references.
+//!
+//! A server may answer a search, or a part of it, by pointing to other servers holding the data.
+//! This happens e.g. when searching across the domains of an Active Directory forest.
+//!
+//! A referral result means that the server didn't do the search at all, the whole of it is elsewhere.
+//! Search continuation references come in the middle of an otherwise normal search,
+//! telling that some subtrees of the search are elsewhere.
+
+use std::{num::NonZeroU8, num::NonZeroU16, sync::Arc, time::Duration};
+
+use futures::{
+    StreamExt,
+    stream::{self, BoxStream},
+};
+use ldap3::Scope;
+use tracing::{debug, warn};
+use url::Url;
+
+use crate::{
+    Error, LdapConfig, Record, SearchOptions, SortBy, TlsMode, connect_and_bind, search_adapters,
+    start_search_error, stream::to_native_stream,
+};
+
+/// What to do with the referrals returned by a search.
+///
+/// Set it in [`SearchOptions::referrals`].
+///
+/// Chasing only applies to the streaming searches, [`streaming_search`](crate::LdapClient::streaming_search)
+/// and the ones built on it. The single record searches like [`search`](crate::LdapClient::search)
+/// return [`Error::Referral`] for a referral result.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+pub enum ReferralPolicy {
+    /// Skip the search continuation references.
+    ///
+    /// A referral result still ends the search with [`Error::Referral`],
+    /// as there's nothing to return in its place.
+    #[default]
+    Ignore,
+    /// Yield the search continuation references as [`Error::Referral`] items.
+    ///
+    /// The stream goes on after them.
+    Surface,
+    /// Search the referred servers too.
+    ///
+    /// The referred servers are connected to and bound with the same configuration,
+    /// and using the same TLS mode.
+    /// Each referral gets a new connection, closed when its part of the search is done.
+    ///
+    /// Only chase referrals from servers you trust to point to other trusted servers:
+    ///
+    /// * The credentials of the configuration, `bind_password` included, are sent to the referred servers.
+    /// * With [`TlsMode::None`] they would go unencrypted. Chasing over plaintext is refused
+    ///   unless `allow_plaintext` is set.
+    /// * The pinned certificates of [`TlsOptions`](crate::TlsOptions) apply to the referred servers too.
+    ///   Pin their certificates as well, or the chasing fails.
+    ///
+    /// The scheme of the referral URL is changed to match the TLS mode. An explicit port in the URL
+    /// is then dropped, as it's for the other scheme, and the default port of the TLS mode is used.
+    Chase {
+        /// How many referrals deep to follow.
+        ///
+        /// Referrals past the limit are surfaced.
+        hop_limit: NonZeroU8,
+        /// Chase referrals over unencrypted connections, credentials included.
+        ///
+        /// A referral to an `ldap://` URL with [`TlsMode::None`] fails otherwise.
+        allow_plaintext: bool,
+    },
+}
+
+/// The error for being referred elsewhere.
+pub(crate) fn referral_error(urls: Vec<String>) -> Error {
+    Error::Referral(format!("Referred to other servers: {urls:?}"), urls)
+}
+
+/// The parts of a streaming search needed to continue it in another server.
+pub(crate) struct ReferralChase {
+    pub(crate) config: Arc<LdapConfig>,
+    pub(crate) scope: Scope,
+    pub(crate) filter: String,
+    pub(crate) attributes: Vec<String>,
+    pub(crate) page_size: Option<NonZeroU16>,
+    pub(crate) sort_by: Vec<SortBy>,
+    pub(crate) search_options: SearchOptions,
+    pub(crate) operation_timeout: Option<Duration>,
+    pub(crate) allow_plaintext: bool,
+}
+
+impl ReferralChase {
+    /// Replace the referrals in the stream with the records from the referred servers.
+    pub(crate) fn chase<'a>(
+        self: Arc<Self>,
+        records: impl futures::Stream<Item = Result<Record, Error>> + Send + 'a,
+        hops_left: u8,
+    ) -> BoxStream<'a, Result<Record, Error>> {
+        records
+            .flat_map(move |record| match record {
+                Err(Error::Referral(message, urls)) if hops_left == 0 => {
+                    warn!("Not chasing {urls:?}, the hop limit has been reached.");
+                    stream::iter([Err(Error::Referral(message, urls))]).boxed()
+                }
+                Err(Error::Referral(_, urls)) => Arc::clone(&self).follow(urls, hops_left - 1),
+                other => stream::iter([other]).boxed(),
+            })
+            .boxed()
+    }
+
+    fn follow(
+        self: Arc<Self>,
+        urls: Vec<String>,
+        hops_left: u8,
+    ) -> BoxStream<'static, Result<Record, Error>> {
+        stream::once(self.search_any(urls, hops_left))
+            .flat_map(|result| match result {
+                Ok(records) => records,
+                Err(error) => stream::iter([Err(error)]).boxed(),
+            })
+            .boxed()
+    }
+
+    /// The URLs of a single referral are alternatives, the first one working is enough.
+    async fn search_any(
+        self: Arc<Self>,
+        urls: Vec<String>,
+        hops_left: u8,
+    ) -> Result<BoxStream<'static, Result<Record, Error>>, Error> {
+        let mut last_error = None;
+        for url in &urls {
+            match self.search_at(url).await {
+                Ok(records) => return Ok(Arc::clone(&self).chase(records, hops_left)),
+                Err(error) => {
+                    warn!("Failed to chase the referral to {url}: {error}");
+                    last_error = Some(error);
+                }
+            }
+        }
+
+        Err(last_error.unwrap_or_else(|| referral_error(urls)))
+    }
+
+    async fn search_at(
+        &self,
+        url: &str,
+    ) -> Result<impl futures::Stream<Item = Result<Record, Error>> + Send + 'static, Error> {
+        let target = ReferralTarget::parse(url, self.scope, &self.filter, self.config.tls)?;
+        if target.server.scheme() == "ldap"
+            && self.config.tls == TlsMode::None
+            && !self.allow_plaintext
+        {
+            return Err(Error::Config(format!(
+                "Not chasing the referral {url} over an unencrypted connection, plaintext isn't allowed."
+            )));
+        }
+        debug!("Chasing a referral to {url}");
+
+        let config = LdapConfig {
+            ldap_url: target.server,
+            ..LdapConfig::clone(&self.config)
+        };
+        let (mut ldap, _, _) = connect_and_bind(&config).await?;
+        if let Some(timeout) = self.operation_timeout {
+            ldap.with_timeout(timeout);
+        }
+        self.search_options.apply(&mut ldap);
+
+        let search_stream = ldap
+            .streaming_search_with(
+                search_adapters(self.page_size, self.sort_by.clone())?,
+                &target.base,
+                target.scope,
+                &target.filter,
+                self.attributes.clone(),
+            )
+            .await
+            .map_err(start_search_error)?;
+
+        to_native_stream(search_stream, self.search_options.referrals)
+    }
+}
+
+/// Where a referral URL points to.
+#[derive(Debug, PartialEq)]
+struct ReferralTarget {
+    server: Url,
+    base: String,
+    scope: Scope,
+    filter: String,
+}
+
+impl ReferralTarget {
+    /// The URL may override the scope and the filter of the original search.
+    ///
+    /// The scheme follows the TLS mode so that chasing never goes less secure than the original connection.
+    /// A port given for the other scheme wouldn't be right, so it's replaced with the default one.
+    fn parse(url: &str, scope: Scope, filter: &str, tls: TlsMode) -> Result<Self, Error> {
+        let invalid_url = |error: ldap3::LdapError| {
+            Error::Query(format!("Invalid referral URL {url}"), Box::new(error))
+        };
+        let mut server = Url::parse(url).map_err(|error| invalid_url(error.into()))?;
+        let params = ldap3::get_url_params(&server).map_err(invalid_url)?;
+
+        // The parameters are positional, with empty ones meaning the defaults.
+        let mut query = server.query().unwrap_or_default().split('?').skip(1);
+        let scope = match query.next() {
+            Some("") | None => scope,
+            Some(_) => params.scope,
+        };
+        let filter = match query.next() {
+            Some("") | None => filter.to_owned(),
+            Some(_) => params.filter.into_owned(),
+        };
+        let base = params.base.into_owned();
+
+        let scheme = match tls {
+            TlsMode::None => server.scheme().to_owned(),
+            TlsMode::Ldaps => String::from("ldaps"),
+            TlsMode::StartTls => String::from("ldap"),
+        };
+        if scheme != server.scheme() {
+            let scheme_error =
+                |()| Error::Config(format!("Can't chase the referral {url} with {tls:?}"));
+            server.set_scheme(&scheme).map_err(scheme_error)?;
+            server.set_port(None).map_err(scheme_error)?;
+        }
+
+        Ok(Self {
+            server,
+            base,
+            scope,
+            filter,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::BindMethod;
+    use futures::StreamExt;
+    use ldap3::SearchEntry;
+    use std::collections::HashMap;
+
+    fn chase(tls: TlsMode, allow_plaintext: bool) -> Arc<ReferralChase> {
+        let url = match tls {
+            TlsMode::Ldaps => "ldaps://localhost:1636",
+            TlsMode::None | TlsMode::StartTls => "ldap://localhost:1389",
+        };
+        Arc::new(ReferralChase {
+            config: Arc::new(LdapConfig {
+                bind_dn: Some(String::from("cn=manager")),
+                bind_password: Some(String::from("password")),
+                ldap_url: Url::parse(url).unwrap(),
+                tls,
+                tls_options: None,
+                bind_method: BindMethod::Simple,
+                dn_attribute: None,
+                retry: None,
+                operation_timeout: None,
+                password_policy: false,
+                connection_settings: None,
+            }),
+            scope: Scope::Subtree,
+            filter: String::from("(cn=Sam)"),
+            attributes: vec![String::from("cn")],
+            page_size: None,
+            sort_by: Vec::new(),
+            search_options: SearchOptions::default(),
+            operation_timeout: None,
+            allow_plaintext,
+        })
+    }
+
+    fn record(dn: &str) -> Record {
+        Record {
+            search_entry: SearchEntry {
+                dn: dn.to_owned(),
+                attrs: HashMap::new(),
+                bin_attrs: HashMap::new(),
+            },
+        }
+    }
+
+    const REFERRAL_URL: &str = "ldap://dc2.example.com/ou=people,dc=child,dc=example,dc=com";
+
+    #[tokio::test]
+    async fn hop_limit_reached() {
+        let records = stream::iter([
+            Ok(record("uid=sam,ou=people,dc=example,dc=com")),
+            Err(referral_error(vec![REFERRAL_URL.to_owned()])),
+        ]);
+
+        let results: Vec<_> = chase(TlsMode::None, false)
+            .chase(records, 0)
+            .collect()
+            .await;
+
+        match results.as_slice() {
+            [Ok(record), Err(Error::Referral(_, urls))] => {
+                assert_eq!(
+                    record.search_entry.dn,
+                    "uid=sam,ou=people,dc=example,dc=com"
+                );
+                assert_eq!(urls, &[REFERRAL_URL]);
+            }
+            _ => panic!("Expected the record and the referral"),
+        }
+    }
+
+    #[tokio::test]
+    async fn plaintext_not_chased() {
+        let records = stream::iter([Err(referral_error(vec![REFERRAL_URL.to_owned()]))]);
+
+        // Refused before connecting anywhere.
+        let results: Vec<_> = chase(TlsMode::None, false)
+            .chase(records, 1)
+            .collect()
+            .await;
+
+        assert!(
+            matches!(results.as_slice(), [Err(Error::Config(_))]),
+            "{:?}",
+            results
+                .iter()
+                .map(Result::as_ref)
+                .map(Result::err)
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[tokio::test]
+    async fn every_reference_chased() {
+        // The URLs of one reference are alternatives, the references themselves are different subtrees.
+        let records = stream::iter([
+            Err(referral_error(vec![
+                REFERRAL_URL.to_owned(),
+                String::from("ldap://dc3.example.com/ou=people,dc=child,dc=example,dc=com"),
+            ])),
+            Ok(record("uid=sam,ou=people,dc=example,dc=com")),
+            Err(referral_error(vec![String::from(
+                "ldap://dc4.example.com/ou=people,dc=other,dc=example,dc=com",
+            )])),
+        ]);
+
+        let results: Vec<_> = chase(TlsMode::None, false)
+            .chase(records, 1)
+            .collect()
+            .await;
+
+        assert!(
+            matches!(
+                results.as_slice(),
+                [Err(Error::Config(_)), Ok(_), Err(Error::Config(_))]
+            ),
+            "{:?}",
+            results
+                .iter()
+                .map(Result::as_ref)
+                .map(Result::err)
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[tokio::test]
+    async fn follows_referrals_within_the_hop_limit() {
+        // Nothing listens in the referred port, so following ends in a connection error
+        // instead of the referral.
+        let records = stream::iter([Err(referral_error(vec![String::from(
+            "ldap://localhost:9/dc=example,dc=com",
+        )]))]);
+
+        let results: Vec<_> = chase(TlsMode::None, true).chase(records, 1).collect().await;
+
+        assert!(
+            matches!(results.as_slice(), [Err(Error::Connection(..))]),
+            "{:?}",
+            results
+                .iter()
+                .map(Result::as_ref)
+                .map(Result::err)
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn continuation_reference() {
+        let target = ReferralTarget::parse(
+            "ldap://dc2.example.com/ou=people,dc=child,dc=example,dc=com",
+            Scope::Subtree,
+            "(cn=Sam)",
+            TlsMode::StartTls,
+        )
+        .unwrap();
+
+        assert_eq!(target.server.host_str(), Some("dc2.example.com"));
+        assert_eq!(target.server.scheme(), "ldap");
+        assert_eq!(target.base, "ou=people,dc=child,dc=example,dc=com");
+        assert_eq!(target.scope, Scope::Subtree);
+        assert_eq!(target.filter, "(cn=Sam)");
+    }
+
+    #[test]
+    fn url_overrides_the_search() {
+        let target = ReferralTarget::parse(
+            "ldap://dc2.example.com:3268/dc=example,dc=com??base?(uid=sam)",
+            Scope::OneLevel,
+            "(cn=Sam)",
+            TlsMode::StartTls,
+        )
+        .unwrap();
+
+        assert_eq!(target.server.scheme(), "ldap");
+        assert_eq!(target.server.port(), Some(3268));
+        assert_eq!(target.scope, Scope::Base);
+        assert_eq!(target.filter, "(uid=sam)");
+    }
+
+    #[test]
+    fn upgraded_scheme_drops_the_port() {
+        let target = ReferralTarget::parse(
+            "ldap://dc2.example.com:389/dc=example,dc=com",
+            Scope::Subtree,
+            "(cn=Sam)",
+            TlsMode::Ldaps,
+        )
+        .unwrap();
+
+        assert_eq!(target.server.scheme(), "ldaps");
+        assert_eq!(target.server.port(), None);
+        assert_eq!(target.server.host_str(), Some("dc2.example.com"));
+    }
+
+    #[test]
+    fn invalid_url() {
+        let result = ReferralTarget::parse("not a url", Scope::Base, "", TlsMode::None);
+        assert!(matches!(result, Err(Error::Query(..))));
+    }
+}
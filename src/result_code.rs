@@ -0,0 +1,250 @@
+//! LDAP result codes.
+//!
+//! Every LDAP operation ends with a numeric result code telling whether it succeeded,
+//! and if not, why. The codes are listed in [RFC 4511 Appendix A](https://datatracker.ietf.org/doc/html/rfc4511#appendix-A).
+//!
+//! There's a nice human readable reference at [ldap.com](https://ldap.com/ldap-result-code-reference/).
+
+use std::fmt;
+
+/// A typed LDAP result code.
+///
+/// Covers the codes defined in RFC 4511. Anything else the server might send
+/// (vendor extensions, client side codes) ends up in `Unknown`.
+///
+/// ```
+/// use simple_ldap::ResultCode;
+///
+/// assert_eq!(ResultCode::from(68), ResultCode::EntryAlreadyExists);
+/// assert_eq!(u32::from(ResultCode::Busy), 51);
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum ResultCode {
+    Success,
+    OperationsError,
+    ProtocolError,
+    TimeLimitExceeded,
+    SizeLimitExceeded,
+    CompareFalse,
+    CompareTrue,
+    AuthMethodNotSupported,
+    StrongerAuthRequired,
+    Referral,
+    AdminLimitExceeded,
+    UnavailableCriticalExtension,
+    ConfidentialityRequired,
+    SaslBindInProgress,
+    NoSuchAttribute,
+    UndefinedAttributeType,
+    InappropriateMatching,
+    ConstraintViolation,
+    AttributeOrValueExists,
+    InvalidAttributeSyntax,
+    NoSuchObject,
+    AliasProblem,
+    InvalidDnSyntax,
+    AliasDereferencingProblem,
+    InappropriateAuthentication,
+    InvalidCredentials,
+    InsufficientAccessRights,
+    Busy,
+    Unavailable,
+    UnwillingToPerform,
+    LoopDetect,
+    NamingViolation,
+    ObjectClassViolation,
+    NotAllowedOnNonLeaf,
+    NotAllowedOnRdn,
+    EntryAlreadyExists,
+    ObjectClassModsProhibited,
+    AffectsMultipleDsas,
+    Other,
+    /// A code not defined in RFC 4511.
+    Unknown(u32),
+}
+
+impl ResultCode {
+    /// The numeric representation used on the wire.
+    pub fn code(self) -> u32 {
+        match self {
+            Self::Success => 0,
+            Self::OperationsError => 1,
+            Self::ProtocolError => 2,
+            Self::TimeLimitExceeded => 3,
+            Self::SizeLimitExceeded => 4,
+            Self::CompareFalse => 5,
+            Self::CompareTrue => 6,
+            Self::AuthMethodNotSupported => 7,
+            Self::StrongerAuthRequired => 8,
+            Self::Referral => 10,
+            Self::AdminLimitExceeded => 11,
+            Self::UnavailableCriticalExtension => 12,
+            Self::ConfidentialityRequired => 13,
+            Self::SaslBindInProgress => 14,
+            Self::NoSuchAttribute => 16,
+            Self::UndefinedAttributeType => 17,
+            Self::InappropriateMatching => 18,
+            Self::ConstraintViolation => 19,
+            Self::AttributeOrValueExists => 20,
+            Self::InvalidAttributeSyntax => 21,
+            Self::NoSuchObject => 32,
+            Self::AliasProblem => 33,
+            Self::InvalidDnSyntax => 34,
+            Self::AliasDereferencingProblem => 36,
+            Self::InappropriateAuthentication => 48,
+            Self::InvalidCredentials => 49,
+            Self::InsufficientAccessRights => 50,
+            Self::Busy => 51,
+            Self::Unavailable => 52,
+            Self::UnwillingToPerform => 53,
+            Self::LoopDetect => 54,
+            Self::NamingViolation => 64,
+            Self::ObjectClassViolation => 65,
+            Self::NotAllowedOnNonLeaf => 66,
+            Self::NotAllowedOnRdn => 67,
+            Self::EntryAlreadyExists => 68,
+            Self::ObjectClassModsProhibited => 69,
+            Self::AffectsMultipleDsas => 71,
+            Self::Other => 80,
+            Self::Unknown(code) => code,
+        }
+    }
+
+    /// Is it worth trying the same operation again a bit later?
+    ///
+    /// These are the codes by which the server tells that it's temporarily unable
+    /// to serve us, rather than that there's something wrong with the request.
+    pub fn is_retryable(self) -> bool {
+        matches!(self, Self::Busy | Self::Unavailable)
+    }
+}
+
+impl From<u32> for ResultCode {
+    fn from(code: u32) -> Self {
+        match code {
+            0 => Self::Success,
+            1 => Self::OperationsError,
+            2 => Self::ProtocolError,
+            3 => Self::TimeLimitExceeded,
+            4 => Self::SizeLimitExceeded,
+            5 => Self::CompareFalse,
+            6 => Self::CompareTrue,
+            7 => Self::AuthMethodNotSupported,
+            8 => Self::StrongerAuthRequired,
+            10 => Self::Referral,
+            11 => Self::AdminLimitExceeded,
+            12 => Self::UnavailableCriticalExtension,
+            13 => Self::ConfidentialityRequired,
+            14 => Self::SaslBindInProgress,
+            16 => Self::NoSuchAttribute,
+            17 => Self::UndefinedAttributeType,
+            18 => Self::InappropriateMatching,
+            19 => Self::ConstraintViolation,
+            20 => Self::AttributeOrValueExists,
+            21 => Self::InvalidAttributeSyntax,
+            32 => Self::NoSuchObject,
+            33 => Self::AliasProblem,
+            34 => Self::InvalidDnSyntax,
+            36 => Self::AliasDereferencingProblem,
+            48 => Self::InappropriateAuthentication,
+            49 => Self::InvalidCredentials,
+            50 => Self::InsufficientAccessRights,
+            51 => Self::Busy,
+            52 => Self::Unavailable,
+            53 => Self::UnwillingToPerform,
+            54 => Self::LoopDetect,
+            64 => Self::NamingViolation,
+            65 => Self::ObjectClassViolation,
+            66 => Self::NotAllowedOnNonLeaf,
+            67 => Self::NotAllowedOnRdn,
+            68 => Self::EntryAlreadyExists,
+            69 => Self::ObjectClassModsProhibited,
+            71 => Self::AffectsMultipleDsas,
+            80 => Self::Other,
+            other => Self::Unknown(other),
+        }
+    }
+}
+
+impl From<ResultCode> for u32 {
+    fn from(value: ResultCode) -> Self {
+        value.code()
+    }
+}
+
+/// Prints the RFC name followed by the numeric code. E.g. "entryAlreadyExists (68)"
+impl fmt::Display for ResultCode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Self::Success => "success",
+            Self::OperationsError => "operationsError",
+            Self::ProtocolError => "protocolError",
+            Self::TimeLimitExceeded => "timeLimitExceeded",
+            Self::SizeLimitExceeded => "sizeLimitExceeded",
+            Self::CompareFalse => "compareFalse",
+            Self::CompareTrue => "compareTrue",
+            Self::AuthMethodNotSupported => "authMethodNotSupported",
+            Self::StrongerAuthRequired => "strongerAuthRequired",
+            Self::Referral => "referral",
+            Self::AdminLimitExceeded => "adminLimitExceeded",
+            Self::UnavailableCriticalExtension => "unavailableCriticalExtension",
+            Self::ConfidentialityRequired => "confidentialityRequired",
+            Self::SaslBindInProgress => "saslBindInProgress",
+            Self::NoSuchAttribute => "noSuchAttribute",
+            Self::UndefinedAttributeType => "undefinedAttributeType",
+            Self::InappropriateMatching => "inappropriateMatching",
+            Self::ConstraintViolation => "constraintViolation",
+            Self::AttributeOrValueExists => "attributeOrValueExists",
+            Self::InvalidAttributeSyntax => "invalidAttributeSyntax",
+            Self::NoSuchObject => "noSuchObject",
+            Self::AliasProblem => "aliasProblem",
+            Self::InvalidDnSyntax => "invalidDNSyntax",
+            Self::AliasDereferencingProblem => "aliasDereferencingProblem",
+            Self::InappropriateAuthentication => "inappropriateAuthentication",
+            Self::InvalidCredentials => "invalidCredentials",
+            Self::InsufficientAccessRights => "insufficientAccessRights",
+            Self::Busy => "busy",
+            Self::Unavailable => "unavailable",
+            Self::UnwillingToPerform => "unwillingToPerform",
+            Self::LoopDetect => "loopDetect",
+            Self::NamingViolation => "namingViolation",
+            Self::ObjectClassViolation => "objectClassViolation",
+            Self::NotAllowedOnNonLeaf => "notAllowedOnNonLeaf",
+            Self::NotAllowedOnRdn => "notAllowedOnRDN",
+            Self::EntryAlreadyExists => "entryAlreadyExists",
+            Self::ObjectClassModsProhibited => "objectClassModsProhibited",
+            Self::AffectsMultipleDsas => "affectsMultipleDSAs",
+            Self::Other => "other",
+            Self::Unknown(_) => "unknown",
+        };
+
+        write!(f, "{name} ({})", self.code())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn code_round_trip() {
+        for code in 0..=200 {
+            assert_eq!(ResultCode::from(code).code(), code);
+        }
+    }
+
+    #[test]
+    fn unknown_codes() {
+        assert_eq!(ResultCode::from(9), ResultCode::Unknown(9));
+        assert_eq!(ResultCode::from(4096), ResultCode::Unknown(4096));
+    }
+
+    #[test]
+    fn display() {
+        assert_eq!(
+            ResultCode::EntryAlreadyExists.to_string(),
+            "entryAlreadyExists (68)"
+        );
+        assert_eq!(ResultCode::Unknown(123).to_string(), "unknown (123)");
+    }
+}
@@ -0,0 +1,151 @@
+//! Reconnecting and retrying after connection failures.
+//!
+//! A server restart or a load balancer dropping idle TCP connections leaves the
+//! connection dead for good. With a [`RetryPolicy`] configured, the client opens and binds
+//! a new connection and tries again, waiting a bit longer after each failure.
+//!
+//! Only idempotent operations are retried. Retrying e.g. an add after the connection broke
+//! might add the entry twice, or fail because the first attempt actually made it through.
+//! Such operations fail with the connection error instead, but the client still reconnects,
+//! so that the next operations work again.
+//! A streaming search is retried only until it starts.
+
+use std::{future::Future, time::Duration};
+
+use tracing::warn;
+
+use crate::Error;
+
+/// How to retry operations failing because of the connection.
+///
+/// Set it in [`LdapConfig::retry`](crate::LdapConfig::retry) to enable reconnecting.
+///
+/// The waits grow exponentially: `initial_backoff`, twice that, four times that
+/// and so on, up to `max_backoff`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RetryPolicy {
+    /// How many times to retry after the first attempt.
+    pub max_retries: u32,
+    /// How long to wait before the first retry.
+    pub initial_backoff: Duration,
+    /// Upper limit for the wait between retries.
+    pub max_backoff: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_retries: 3,
+            initial_backoff: Duration::from_millis(100),
+            max_backoff: Duration::from_secs(5),
+        }
+    }
+}
+
+/// The state of retrying a single operation.
+#[derive(Debug)]
+pub(crate) struct Backoff<'a> {
+    /// `None` means no retries.
+    policy: Option<&'a RetryPolicy>,
+    retries: u32,
+}
+
+impl<'a> Backoff<'a> {
+    pub(crate) fn new(policy: Option<&'a RetryPolicy>) -> Self {
+        Self { policy, retries: 0 }
+    }
+
+    /// How long to wait before retrying, or `None` if the retries are all used up.
+    pub(crate) fn next_delay(&mut self) -> Option<Duration> {
+        let policy = self.policy?;
+        if self.retries >= policy.max_retries {
+            return None;
+        }
+
+        let delay = 2_u32
+            .checked_pow(self.retries)
+            .and_then(|factor| policy.initial_backoff.checked_mul(factor))
+            .map_or(policy.max_backoff, |delay| delay.min(policy.max_backoff));
+        self.retries += 1;
+
+        Some(delay)
+    }
+
+    /// Wait before retrying after the error, if the error and the policy allow it.
+    ///
+    /// Returns `false` if the error should be given up on.
+    pub(crate) async fn wait(&mut self, error: &Error) -> bool {
+        if !(error.is_connection_error() || error.is_retryable()) {
+            return false;
+        }
+
+        let Some(delay) = self.next_delay() else {
+            return false;
+        };
+
+        warn!("Retrying in {delay:?} after: {error}");
+        tokio::time::sleep(delay).await;
+
+        true
+    }
+}
+
+/// Keep trying until success, an error not worth retrying, or running out of retries.
+///
+/// Only for attempts that don't need the client's connection, e.g. opening a new one.
+pub(crate) async fn retry<T, F, Fut>(
+    policy: Option<&RetryPolicy>,
+    mut attempt: F,
+) -> Result<T, Error>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T, Error>>,
+{
+    let mut backoff = Backoff::new(policy);
+    loop {
+        match attempt().await {
+            Err(error) if backoff.wait(&error).await => continue,
+            result => return result,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::iter;
+
+    use super::*;
+
+    #[test]
+    fn no_policy_no_retries() {
+        assert_eq!(Backoff::new(None).next_delay(), None);
+    }
+
+    #[test]
+    fn exponential_delays() {
+        let policy = RetryPolicy {
+            max_retries: 5,
+            initial_backoff: Duration::from_millis(100),
+            max_backoff: Duration::from_millis(500),
+        };
+        let mut backoff = Backoff::new(Some(&policy));
+
+        let delays: Vec<_> = iter::from_fn(|| backoff.next_delay()).collect();
+        assert_eq!(delays, [100, 200, 400, 500, 500].map(Duration::from_millis));
+    }
+
+    #[test]
+    fn huge_exponents_stay_capped() {
+        let policy = RetryPolicy {
+            max_retries: 100,
+            initial_backoff: Duration::from_secs(1),
+            max_backoff: Duration::from_secs(30),
+        };
+        let mut backoff = Backoff::new(Some(&policy));
+
+        assert_eq!(
+            iter::from_fn(|| backoff.next_delay()).last(),
+            Some(Duration::from_secs(30))
+        );
+    }
+}
@@ -0,0 +1,138 @@
+//! Options for the search operations.
+//!
+//! These map to the parameters of the LDAP SearchRequest that the search helpers
+//! otherwise leave to the server's defaults.
+
+use std::{num::NonZeroU32, time::Duration};
+
+use ldap3::{DerefAliases, Ldap, LdapResult, controls::RawControl};
+
+use crate::{ReferralPolicy, ResultCode};
+
+/// Options applied to the searches of a client.
+///
+/// Set them with [`LdapClient::with_search_options`](crate::LdapClient::with_search_options).
+///
+/// ```
+/// use simple_ldap::SearchOptions;
+/// use std::{num::NonZeroU32, time::Duration};
+///
+/// let options = SearchOptions {
+///     size_limit: NonZeroU32::new(100),
+///     time_limit: Some(Duration::from_secs(5)),
+///     ..SearchOptions::default()
+/// };
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct SearchOptions {
+    /// Ask the server to return at most this many entries.
+    ///
+    /// `None` leaves it to the server's own limit.
+    pub size_limit: Option<NonZeroU32>,
+    /// Ask the server to spend at most this long on the search.
+    ///
+    /// The protocol counts in whole seconds, so this gets rounded up.
+    /// `None` leaves it to the server's own limit.
+    pub time_limit: Option<Duration>,
+    /// What to do with referrals.
+    pub referrals: ReferralPolicy,
+    /// Whether the server should dereference the alias entries it comes across.
+    ///
+    /// The default is never.
+    pub deref_aliases: DerefAliases,
+    /// Return just the names of the attributes, without their values.
+    ///
+    /// This is handy for finding out what attributes the entries have,
+    /// see [`Record::attribute_names`](crate::Record::attribute_names).
+    pub types_only: bool,
+    /// Additional controls to send with the search requests.
+    ///
+    /// These are sent in addition to the ones needed for paging and sorting.
+    pub controls: Vec<RawControl>,
+}
+
+impl SearchOptions {
+    /// The options in the form ldap3 takes them.
+    pub(crate) fn to_ldap3(&self) -> ldap3::SearchOptions {
+        // Zero means no limit in the protocol.
+        let size_limit = self
+            .size_limit
+            .map_or(0, |limit| i32::try_from(limit.get()).unwrap_or(i32::MAX));
+        let time_limit = self.time_limit.map_or(0, |limit| {
+            let seconds = limit.as_secs() + u64::from(limit.subsec_nanos() > 0);
+            i32::try_from(seconds.max(1)).unwrap_or(i32::MAX)
+        });
+
+        ldap3::SearchOptions::new()
+            .sizelimit(size_limit)
+            .timelimit(time_limit)
+            .deref(self.deref_aliases)
+            .typesonly(self.types_only)
+    }
+
+    /// Set the options for the next search on the handle.
+    pub(crate) fn apply(&self, ldap: &mut Ldap) {
+        ldap.with_search_options(self.to_ldap3());
+        if !self.controls.is_empty() {
+            ldap.with_controls(self.controls.clone());
+        }
+    }
+}
+
+/// A limit that cut a search short.
+///
+/// Carried by [`Error::LimitExceeded`](crate::Error::LimitExceeded).
+/// The server may enforce limits of its own, even if none were set in [`SearchOptions`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum SearchLimit {
+    /// The search matched more entries than allowed.
+    Size,
+    /// The search took longer than allowed.
+    Time,
+}
+
+impl SearchLimit {
+    /// The limit the search result tells about, if any.
+    pub(crate) fn from_result(result: &LdapResult) -> Option<Self> {
+        match ResultCode::from(result.rc) {
+            ResultCode::SizeLimitExceeded => Some(Self::Size),
+            ResultCode::TimeLimitExceeded => Some(Self::Time),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn no_limits_by_default() {
+        let options = SearchOptions::default().to_ldap3();
+        assert_eq!(options.sizelimit, 0);
+        assert_eq!(options.timelimit, 0);
+        assert_eq!(options.deref, DerefAliases::Never);
+        assert!(!options.typesonly);
+    }
+
+    #[test]
+    fn limits_in_protocol_units() {
+        let options = SearchOptions {
+            size_limit: NonZeroU32::new(u32::MAX),
+            time_limit: Some(Duration::from_millis(1500)),
+            ..SearchOptions::default()
+        }
+        .to_ldap3();
+        assert_eq!(options.sizelimit, i32::MAX);
+        assert_eq!(options.timelimit, 2);
+
+        // Zero would mean no limit at all.
+        let options = SearchOptions {
+            size_limit: None,
+            time_limit: Some(Duration::ZERO),
+            ..SearchOptions::default()
+        }
+        .to_ldap3();
+        assert_eq!(options.timelimit, 1);
+    }
+}
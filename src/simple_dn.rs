@@ -123,6 +123,45 @@ pub fn common_ancestor(left: &SimpleDN, right: &SimpleDN) -> Option<SimpleDN> {
     }
 }
 
+/// Count the RDNs of any DN string, even the ones `SimpleDN` can't represent.
+///
+/// I.e. the depth of the entry in the directory tree.
+pub(crate) fn rdn_count(dn: &str) -> usize {
+    if dn.is_empty() {
+        return 0;
+    }
+
+    rdn_separators(dn).count() + 1
+}
+
+/// Split the first RDN off any DN string, even the ones `SimpleDN` can't represent.
+///
+/// Returns the RDN and the parent DN, if any. Both are slices of the original string, untouched.
+pub(crate) fn split_rdn(dn: &str) -> (&str, Option<&str>) {
+    match rdn_separators(dn).next() {
+        Some(index) => (&dn[..index], Some(&dn[index + 1..])),
+        None => (dn, None),
+    }
+}
+
+/// The byte indices of the commas separating the RDNs, skipping the escaped ones.
+fn rdn_separators(dn: &str) -> impl Iterator<Item = usize> + '_ {
+    let mut escaped = false;
+    dn.char_indices()
+        .filter(move |(_, c)| match (escaped, c) {
+            (true, _) => {
+                escaped = false;
+                false
+            }
+            (false, '\\') => {
+                escaped = true;
+                false
+            }
+            (false, c) => *c == ',',
+        })
+        .map(|(index, _)| index)
+}
+
 fn simple_dn_parser<'src>() -> impl Parser<'src, &'src str, SimpleDN, extra::Err<Rich<'src, char>>>
 {
     simple_rdn_parser()
@@ -176,6 +215,17 @@ impl SimpleDN {
             .key
     }
 
+    /// Get the relative DN of this one. I.e. the leftmost key-value pair.
+    ///
+    /// E.g. The RDN of "CN=Sencha,OU=Green,DC=Tea" is "CN=Sencha".
+    pub fn rdn(&self) -> String {
+        #[allow(clippy::expect_used, reason = "Relying on struct invariant.")]
+        self.rdns
+            .first()
+            .expect("Invariant violation. SimpleDN should never be empty.")
+            .to_string()
+    }
+
     /// Get the parent DN of this one, if there is one.
     ///
     /// E.g. The parent "OU=Puerh,DC=Tea" is "DC=Tea".
@@ -452,6 +502,32 @@ mod tests {
         assert_eq!(no_parents.parent(), None);
     }
 
+    #[test]
+    fn count_rdns() {
+        assert_eq!(rdn_count(EXAMPLE_DN), 4);
+        assert_eq!(rdn_count(r"CN=Tea\, Green,DC=Japan"), 2);
+        assert_eq!(rdn_count(r"CN=Back\\,DC=Slash"), 2);
+        assert_eq!(rdn_count(""), 0);
+    }
+
+    #[test]
+    fn split_first_rdn() {
+        assert_eq!(
+            split_rdn(r"CN=Tea\, Green+SN=Sencha,DC=Japan,DC=Asia"),
+            (r"CN=Tea\, Green+SN=Sencha", Some("DC=Japan,DC=Asia"))
+        );
+        assert_eq!(
+            split_rdn(r"CN=Back\\,DC=Slash"),
+            (r"CN=Back\\", Some("DC=Slash"))
+        );
+        assert_eq!(split_rdn("DC=Japan"), ("DC=Japan", None));
+    }
+
+    #[test]
+    fn get_rdn() {
+        assert_eq!(example_simple_dn().rdn(), "CN=Yabukita");
+    }
+
     #[test]
     fn get_starting_from() {
         let example_dn = example_simple_dn();
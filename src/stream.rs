@@ -2,11 +2,13 @@
 //!
 
 use futures::Stream;
-use ldap3::{LdapError, LdapResult, SearchEntry, SearchStream, StreamState};
+use ldap3::{LdapError, LdapResult, SearchEntry, SearchStream, StreamState, parse_refs};
 use tokio::{runtime::Handle, task::block_in_place};
 use tracing::{Level, debug, error, instrument, warn};
 
-use crate::{Error, Record};
+use crate::{
+    Error, Record, ReferralPolicy, abandon_timed_out, referral::referral_error, search_error,
+};
 
 /// This wrapper exists solely for the purpose of running some cleanup in `drop()`.
 ///
@@ -130,8 +132,14 @@ where
 }
 
 /// A helper to create native rust streams out of `ldap3::SearchStream`s.
+///
+/// Unless the policy says to ignore them, search continuation references are yielded
+/// as `Error::Referral`, one for each reference. Unlike other errors, these don't end the stream.
+///
+/// The adapters of the search mustn't filter out the references, like `EntriesOnly` does.
 pub(crate) fn to_native_stream<'a, S, A>(
     ldap3_stream: SearchStream<'a, S, A>,
+    referrals: ReferralPolicy,
 ) -> Result<impl Stream<Item = Result<Record, Error>> + 'a + use<'a, S, A>, Error>
 where
     S: AsRef<str> + Send + Sync + 'a,
@@ -143,44 +151,70 @@ where
     };
 
     // Produce the steam itself by unfolding.
-    let stream = futures::stream::try_unfold(stream_wrapper, async |mut search| {
-        match search.search_stream.next().await {
-            // In the middle of the stream. Produce the next result.
-            Ok(Some(result_entry)) => Ok(Some((
-                Record {
-                    search_entry: SearchEntry::construct(result_entry),
-                },
-                search,
-            ))),
-            // Stream is done.
-            Ok(None) => {
-                // Performing the cleanup here before yielding the end of the stream.
-                // This is nice place for this as we're already in an async context.
-                // The alternative is to block on this in `drop()`.
-                // That still has to be called because streams may be dropped mid way too,
-                // but running them to completion is assumed to be the common case.
-                //
-                // Actually we cannot call `self.cleanup()` here because that will send
-                // unnecessary search abandon if the stream had no adaptors:
-                // https://github.com/inejge/ldap3/issues/155
-                //
-                // Just finishing is okay though.
-                let cleanup_result = finish_stream(&mut search.search_stream).await;
+    // `None` state means that the stream has ended, possibly in an error.
+    let stream = futures::stream::unfold(Some(stream_wrapper), move |search| async move {
+        let mut search = search?;
+        loop {
+            return match search.search_stream.next().await {
+                // Not something we'd know what to do with.
+                Ok(Some(result_entry)) if result_entry.is_intermediate() => continue,
+                Ok(Some(result_entry)) if result_entry.is_ref() => {
+                    if referrals == ReferralPolicy::Ignore {
+                        continue;
+                    }
+                    Some((
+                        Err(referral_error(parse_refs(result_entry.0))),
+                        Some(search),
+                    ))
+                }
+                // In the middle of the stream. Produce the next result.
+                Ok(Some(result_entry)) => Some((
+                    Ok(Record {
+                        search_entry: SearchEntry::construct(result_entry),
+                    }),
+                    Some(search),
+                )),
+                // Stream is done.
+                Ok(None) => {
+                    // Performing the cleanup here before yielding the end of the stream.
+                    // This is nice place for this as we're already in an async context.
+                    // The alternative is to block on this in `drop()`.
+                    // That still has to be called because streams may be dropped mid way too,
+                    // but running them to completion is assumed to be the common case.
+                    //
+                    // Actually we cannot call `self.cleanup()` here because that will send
+                    // unnecessary search abandon if the stream had no adaptors:
+                    // https://github.com/inejge/ldap3/issues/155
+                    //
+                    // Just finishing is okay though.
+                    let cleanup_result = finish_stream(&mut search.search_stream).await;
 
-                // Doing the cleanup here (as opposed to drop) also has the advantage that we can
-                // return the potential error.
-                match cleanup_result {
-                    Ok(()) => Ok(None),
-                    Err(ldap_err) => Err(Error::Query(
-                        String::from("Error finishing the streaming search"),
-                        ldap_err,
-                    )),
+                    // Doing the cleanup here (as opposed to drop) also has the advantage that we can
+                    // return the potential error.
+                    match cleanup_result {
+                        Ok(()) => None,
+                        Err(ldap_err) => Some((
+                            Err(search_error(
+                                String::from("Error finishing the streaming search"),
+                                ldap_err,
+                            )),
+                            None,
+                        )),
+                    }
                 }
-            }
-            Err(ldap_error) => Err(Error::Query(
-                format!("Error getting next record: {ldap_error:?}"),
-                ldap_error,
-            )),
+                // The stream is in error state now, so it won't be abandoned on drop.
+                Err(LdapError::Timeout { elapsed }) => Some((
+                    Err(abandon_timed_out(search.search_stream.ldap_handle(), elapsed).await),
+                    None,
+                )),
+                Err(ldap_error) => Some((
+                    Err(Error::Query(
+                        format!("Error getting next record: {ldap_error:?}"),
+                        Box::new(ldap_error),
+                    )),
+                    None,
+                )),
+            };
         }
     });
 
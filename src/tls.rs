@@ -0,0 +1,437 @@
+//! Portable TLS settings.
+//!
+//! [`TlsOptions`] describes how to trust the server and how to identify ourselves to it,
+//! independent of the TLS backend. The options are translated to whichever backend the crate
+//! was compiled with: `native-tls` with the `tls-native` feature or `rustls` with `tls-rustls`.
+//!
+//! ```no_run
+//! use simple_ldap::{BindMethod, LdapConfig, PemSource, TlsMode, TlsOptions};
+//! use url::Url;
+//!
+//! let config = LdapConfig {
+//!     bind_dn: Some(String::from("cn=manager")),
+//!     bind_password: Some(String::from("password")),
+//!     ldap_url: Url::parse("ldaps://ldap.example.com:636/dc=example,dc=com").unwrap(),
+//!     tls: TlsMode::Ldaps,
+//!     tls_options: Some(TlsOptions {
+//!         ca_certificates: vec![PemSource::File("/etc/ssl/private-ca.pem".into())],
+//!         ..TlsOptions::default()
+//!     }),
+//!     bind_method: BindMethod::Simple,
+//!     dn_attribute: None,
+//!     retry: None,
+//!     operation_timeout: None,
+//!     password_policy: false,
+//!     connection_settings: None,
+//! };
+//! ```
+
+use std::{fmt, path::PathBuf, str::FromStr};
+
+use ldap3::{Ldap, LdapConnSettings};
+use sha2::{Digest, Sha256};
+
+use crate::Error;
+
+/// How to verify the server and present ourselves to it.
+///
+/// The default trusts the system's certificate store, presents no client certificate,
+/// and doesn't pin anything. I.e. it's the same as not giving any options.
+#[derive(Debug, Clone, Default)]
+pub struct TlsOptions {
+    /// Additional trusted CA certificates, e.g. for a private CA.
+    ///
+    /// These are trusted on top of the system's certificate store.
+    /// A single source may contain several certificates.
+    pub ca_certificates: Vec<PemSource>,
+    /// Certificate and key presented to the server, if it asks for one.
+    pub client_certificate: Option<ClientCertificate>,
+    /// If not empty, the SHA-256 fingerprint of the server's certificate has to match
+    /// one of these. This is checked in addition to the normal verification, before anything
+    /// else is sent to the server.
+    pub pinned_certificates: Vec<CertificateFingerprint>,
+    /// Accept any server certificate, including expired, self-signed and ones for another host.
+    ///
+    /// This disables the protection TLS offers against impersonating the server.
+    /// Only meant for testing, never use it in production!
+    pub danger_accept_invalid_certificates: bool,
+}
+
+/// Where to read PEM encoded data from.
+#[derive(derive_more::Debug, Clone)]
+pub enum PemSource {
+    /// Read from a file when connecting.
+    File(PathBuf),
+    /// Already in memory.
+    Bytes(#[debug(skip)] Vec<u8>), // Might be a private key.
+}
+
+/// Client certificate for mutual TLS.
+#[derive(Debug, Clone)]
+pub struct ClientCertificate {
+    /// The client certificate, optionally followed by the intermediate certificates.
+    pub certificate_chain: PemSource,
+    /// The private key of the certificate.
+    ///
+    /// Use PKCS #8 (`BEGIN PRIVATE KEY`), as that's the only format supported by all the backends.
+    pub private_key: PemSource,
+}
+
+/// SHA-256 fingerprint of a DER encoded certificate.
+///
+/// Parses from hex with or without colon separators, e.g. the output of
+/// `openssl x509 -noout -fingerprint -sha256`.
+///
+/// ```
+/// use simple_ldap::CertificateFingerprint;
+///
+/// let fingerprint: CertificateFingerprint =
+///     "3F:0E:6E:A4:2B:2D:9A:44:6B:92:9D:43:D6:58:46:3E:4C:0C:E5:60:32:41:6C:05:E5:EE:45:4B:13:58:4A:56"
+///         .parse()
+///         .unwrap();
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct CertificateFingerprint(pub [u8; 32]);
+
+impl CertificateFingerprint {
+    /// Compute the fingerprint of a DER encoded certificate.
+    pub fn of_der(certificate: &[u8]) -> Self {
+        Self(Sha256::digest(certificate).into())
+    }
+}
+
+impl From<[u8; 32]> for CertificateFingerprint {
+    fn from(value: [u8; 32]) -> Self {
+        Self(value)
+    }
+}
+
+/// Error from parsing a [`CertificateFingerprint`].
+#[derive(Debug, thiserror::Error)]
+#[error("Expected a SHA-256 fingerprint as 64 hex digits, got {0:?}")]
+pub struct FingerprintParseError(String);
+
+impl FromStr for CertificateFingerprint {
+    type Err = FingerprintParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let digits = s
+            .trim()
+            .bytes()
+            .filter(|byte| *byte != b':')
+            .collect::<Vec<_>>();
+        if digits.len() != 64 {
+            return Err(FingerprintParseError(s.to_owned()));
+        }
+
+        let mut fingerprint = [0; 32];
+        for (byte, pair) in fingerprint.iter_mut().zip(digits.chunks_exact(2)) {
+            let pair =
+                std::str::from_utf8(pair).map_err(|_| FingerprintParseError(s.to_owned()))?;
+            *byte =
+                u8::from_str_radix(pair, 16).map_err(|_| FingerprintParseError(s.to_owned()))?;
+        }
+
+        Ok(Self(fingerprint))
+    }
+}
+
+/// Colon separated upper case hex, like OpenSSL prints them.
+impl fmt::Display for CertificateFingerprint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (index, byte) in self.0.iter().enumerate() {
+            if index > 0 {
+                f.write_str(":")?;
+            }
+            write!(f, "{byte:02X}")?;
+        }
+        Ok(())
+    }
+}
+
+impl PemSource {
+    #[cfg(any(feature = "tls-native", feature = "tls-rustls"))]
+    fn read(&self) -> Result<Vec<u8>, Error> {
+        match self {
+            Self::File(path) => std::fs::read(path).map_err(|io_err| {
+                Error::Config(format!("Unable to read {}: {io_err}", path.display()))
+            }),
+            Self::Bytes(bytes) => Ok(bytes.clone()),
+        }
+    }
+}
+
+impl TlsOptions {
+    /// Configure the TLS backend of the connection according to these options.
+    ///
+    /// Replaces any connector or config set in the settings beforehand.
+    #[cfg(any(feature = "tls-native", feature = "tls-rustls"))]
+    pub(crate) fn apply(&self, settings: LdapConnSettings) -> Result<LdapConnSettings, Error> {
+        // ldap3 looks at this on its own too, e.g. when connecting to a bare IP address.
+        let settings = settings.set_no_tls_verify(self.danger_accept_invalid_certificates);
+
+        match () {
+            #[cfg(feature = "tls-native")]
+            () => Ok(settings.set_connector(self.native_connector()?)),
+            #[cfg(feature = "tls-rustls")]
+            () => Ok(settings.set_config(std::sync::Arc::new(self.rustls_config()?))),
+        }
+    }
+
+    #[cfg(not(any(feature = "tls-native", feature = "tls-rustls")))]
+    pub(crate) fn apply(&self, _settings: LdapConnSettings) -> Result<LdapConnSettings, Error> {
+        Err(Error::Config(String::from(
+            "TLS options require the tls-native or tls-rustls feature",
+        )))
+    }
+
+    /// Check the server certificate against the pinned fingerprints.
+    ///
+    /// Call this right after connecting, before binding.
+    pub(crate) async fn verify_pinned_certificate(&self, ldap: &mut Ldap) -> Result<(), Error> {
+        if self.pinned_certificates.is_empty() {
+            return Ok(());
+        }
+
+        let certificate = ldap.get_peer_certificate().await.map_err(|ldap_err| {
+            Error::Connection(
+                String::from("Unable to get the server certificate."),
+                Box::new(ldap_err),
+            )
+        })?;
+
+        let Some(certificate) = certificate else {
+            return Err(Error::Tls(String::from(
+                "Certificates are pinned, but the server didn't present one.",
+            )));
+        };
+
+        let fingerprint = CertificateFingerprint::of_der(&certificate);
+        if self.pinned_certificates.contains(&fingerprint) {
+            Ok(())
+        } else {
+            Err(Error::Tls(format!(
+                "The server certificate {fingerprint} doesn't match any of the pinned ones."
+            )))
+        }
+    }
+
+    #[cfg(feature = "tls-native")]
+    fn native_connector(&self) -> Result<native_tls::TlsConnector, Error> {
+        let mut builder = native_tls::TlsConnector::builder();
+
+        for source in &self.ca_certificates {
+            let certificates =
+                native_tls::Certificate::stack_from_pem(&source.read()?).map_err(|tls_err| {
+                    Error::Config(format!("Invalid CA certificate in {source:?}: {tls_err}"))
+                })?;
+            for certificate in certificates {
+                builder.add_root_certificate(certificate);
+            }
+        }
+
+        if let Some(ClientCertificate {
+            certificate_chain,
+            private_key,
+        }) = &self.client_certificate
+        {
+            let identity =
+                native_tls::Identity::from_pkcs8(&certificate_chain.read()?, &private_key.read()?)
+                    .map_err(|tls_err| {
+                        Error::Config(format!("Invalid client certificate or key: {tls_err}"))
+                    })?;
+            builder.identity(identity);
+        }
+
+        if self.danger_accept_invalid_certificates {
+            builder
+                .danger_accept_invalid_certs(true)
+                .danger_accept_invalid_hostnames(true);
+        }
+
+        builder
+            .build()
+            .map_err(|tls_err| Error::Config(format!("Unable to set up TLS: {tls_err}")))
+    }
+
+    #[cfg(feature = "tls-rustls")]
+    fn rustls_config(&self) -> Result<rustls::ClientConfig, Error> {
+        use rustls::{
+            ClientConfig, RootCertStore,
+            pki_types::{CertificateDer, PrivateKeyDer, pem::PemObject},
+        };
+
+        let mut roots = RootCertStore::empty();
+        // Same as ldap3 does by default. Certificates that don't load are just skipped.
+        roots.add_parsable_certificates(rustls_native_certs::load_native_certs().certs);
+
+        for source in &self.ca_certificates {
+            for certificate in CertificateDer::pem_slice_iter(&source.read()?) {
+                let certificate = certificate.map_err(|pem_err| {
+                    Error::Config(format!("Invalid CA certificate in {source:?}: {pem_err}"))
+                })?;
+                roots.add(certificate).map_err(|tls_err| {
+                    Error::Config(format!("Invalid CA certificate in {source:?}: {tls_err}"))
+                })?;
+            }
+        }
+
+        let builder = ClientConfig::builder();
+        let builder = if self.danger_accept_invalid_certificates {
+            let verifier = danger::AcceptAnyCertificate(builder.crypto_provider().clone());
+            builder
+                .dangerous()
+                .with_custom_certificate_verifier(std::sync::Arc::new(verifier))
+        } else {
+            builder.with_root_certificates(roots)
+        };
+
+        match &self.client_certificate {
+            None => Ok(builder.with_no_client_auth()),
+            Some(ClientCertificate {
+                certificate_chain,
+                private_key,
+            }) => {
+                let chain = CertificateDer::pem_slice_iter(&certificate_chain.read()?)
+                    .collect::<Result<Vec<_>, _>>()
+                    .map_err(|pem_err| {
+                        Error::Config(format!("Invalid client certificate: {pem_err}"))
+                    })?;
+                let key =
+                    PrivateKeyDer::from_pem_slice(&private_key.read()?).map_err(|pem_err| {
+                        Error::Config(format!("Invalid client private key: {pem_err}"))
+                    })?;
+
+                builder
+                    .with_client_auth_cert(chain, key)
+                    .map_err(|tls_err| {
+                        Error::Config(format!("Invalid client certificate or key: {tls_err}"))
+                    })
+            }
+        }
+    }
+}
+
+#[cfg(feature = "tls-rustls")]
+mod danger {
+    //! The insecure mode for rustls.
+
+    use std::sync::Arc;
+
+    use rustls::{
+        DigitallySignedStruct, SignatureScheme,
+        client::danger::{HandshakeSignatureValid, ServerCertVerified, ServerCertVerifier},
+        crypto::{CryptoProvider, verify_tls12_signature, verify_tls13_signature},
+        pki_types::{CertificateDer, ServerName, UnixTime},
+    };
+
+    /// Accepts any certificate, but still checks that the server owns it.
+    #[derive(Debug)]
+    pub(super) struct AcceptAnyCertificate(pub(super) Arc<CryptoProvider>);
+
+    impl ServerCertVerifier for AcceptAnyCertificate {
+        fn verify_server_cert(
+            &self,
+            _end_entity: &CertificateDer<'_>,
+            _intermediates: &[CertificateDer<'_>],
+            _server_name: &ServerName<'_>,
+            _ocsp_response: &[u8],
+            _now: UnixTime,
+        ) -> Result<ServerCertVerified, rustls::Error> {
+            Ok(ServerCertVerified::assertion())
+        }
+
+        fn verify_tls12_signature(
+            &self,
+            message: &[u8],
+            cert: &CertificateDer<'_>,
+            dss: &DigitallySignedStruct,
+        ) -> Result<HandshakeSignatureValid, rustls::Error> {
+            verify_tls12_signature(
+                message,
+                cert,
+                dss,
+                &self.0.signature_verification_algorithms,
+            )
+        }
+
+        fn verify_tls13_signature(
+            &self,
+            message: &[u8],
+            cert: &CertificateDer<'_>,
+            dss: &DigitallySignedStruct,
+        ) -> Result<HandshakeSignatureValid, rustls::Error> {
+            verify_tls13_signature(
+                message,
+                cert,
+                dss,
+                &self.0.signature_verification_algorithms,
+            )
+        }
+
+        fn supported_verify_schemes(&self) -> Vec<SignatureScheme> {
+            self.0.signature_verification_algorithms.supported_schemes()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FINGERPRINT: &str = "3F:0E:6E:A4:2B:2D:9A:44:6B:92:9D:43:D6:58:46:3E:4C:0C:E5:60:32:41:6C:05:E5:EE:45:4B:13:58:4A:56";
+
+    #[test]
+    fn parse_fingerprint() {
+        let with_colons: CertificateFingerprint = FINGERPRINT.parse().unwrap();
+        let without_colons: CertificateFingerprint =
+            FINGERPRINT.replace(':', "").to_lowercase().parse().unwrap();
+
+        assert_eq!(with_colons, without_colons);
+        assert_eq!(with_colons.0[0], 0x3F);
+        assert_eq!(with_colons.0[31], 0x56);
+        assert_eq!(with_colons.to_string(), FINGERPRINT);
+    }
+
+    #[test]
+    fn parse_invalid_fingerprint() {
+        assert!("3F:0E".parse::<CertificateFingerprint>().is_err());
+        assert!(
+            FINGERPRINT
+                .replace("3F", "XY")
+                .parse::<CertificateFingerprint>()
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn fingerprint_of_der() {
+        // SHA-256 of the empty input.
+        let expected: CertificateFingerprint =
+            "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855"
+                .parse()
+                .unwrap();
+        assert_eq!(CertificateFingerprint::of_der(&[]), expected);
+    }
+
+    #[cfg(any(feature = "tls-native", feature = "tls-rustls"))]
+    #[test]
+    fn invalid_ca_certificate() {
+        let options = TlsOptions {
+            ca_certificates: vec![PemSource::Bytes(
+                b"-----BEGIN CERTIFICATE-----\nnonsense\n-----END CERTIFICATE-----\n".to_vec(),
+            )],
+            ..TlsOptions::default()
+        };
+
+        let result = options.apply(LdapConnSettings::new());
+        assert!(matches!(result, Err(Error::Config(_))));
+    }
+
+    #[cfg(any(feature = "tls-native", feature = "tls-rustls"))]
+    #[test]
+    fn missing_pem_file() {
+        let source = PemSource::File(PathBuf::from("/surely/this/does/not/exist.pem"));
+        assert!(matches!(source.read(), Err(Error::Config(_))));
+    }
+}
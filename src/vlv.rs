@@ -0,0 +1,65 @@
+//! This module implements the Virtual List View control in `ldap3`'s `Adapter` framework.
+//!
+//! VLV lets a client look at a window of a sorted result set, without fetching everything
+//! before it. It's always used together with Server Side Sort.
+
+// Modelled after the Server Side Sort implementation.
+
+pub(crate) mod adapter;
+
+// Control is the low level component of the implementation.
+mod control;
+
+use crate::SortBy;
+
+const VIRTUAL_LIST_VIEW_REQUEST_OID: &str = "2.16.840.1.113730.3.4.9";
+const VIRTUAL_LIST_VIEW_RESPONSE_OID: &str = "2.16.840.1.113730.3.4.10";
+
+/// What to browse with [`LdapClient::browse`](crate::LdapClient::browse).
+#[derive(Debug, Clone)]
+pub struct ListView {
+    /// How to sort the list. Must not be empty.
+    pub sort_by: Vec<SortBy>,
+    /// Where in the list the window is.
+    pub target: ViewTarget,
+    /// How many entries before the target to include in the window.
+    pub before_count: u32,
+    /// How many entries after the target to include in the window.
+    pub after_count: u32,
+    /// The context ID from the previous [`Window`], if browsing the same list again.
+    ///
+    /// Helps the server to keep the list consistent between the requests.
+    pub context_id: Option<Vec<u8>>,
+}
+
+/// The target entry of the window.
+#[derive(Debug, Clone)]
+pub enum ViewTarget {
+    /// The entry at a position in the list.
+    Offset {
+        /// The position, starting from 1.
+        offset: u32,
+        /// How many entries the client thinks there are in the list, e.g. from a previous [`Window`].
+        ///
+        /// The server scales the offset to its own count, so that e.g. the middle of the list stays the middle.
+        /// Zero means that the offset is to be taken as is.
+        content_count: u32,
+    },
+    /// The first entry whose first sort key is greater than or equal to the value.
+    ///
+    /// E.g. "M" for the names starting with M, when sorting by name.
+    GreaterThanOrEqual(String),
+}
+
+/// A window of a sorted list, returned by [`LdapClient::browse`](crate::LdapClient::browse).
+#[derive(Debug)]
+pub struct Window<T> {
+    /// The entries of the window, in order.
+    pub entries: Vec<T>,
+    /// The position of the target entry in the list, starting from 1.
+    pub target_position: u32,
+    /// How many entries there are in the whole list, as the server estimates it.
+    pub content_count: u32,
+    /// An identifier to pass along with the next request for the same list.
+    pub context_id: Option<Vec<u8>>,
+}
@@ -0,0 +1,141 @@
+//! This module implements the Virtual List View (VLV) search extension
+//! as described in [draft-ietf-ldapext-ldapv3-vlv-09](https://datatracker.ietf.org/doc/html/draft-ietf-ldapext-ldapv3-vlv-09).
+
+use async_trait::async_trait;
+use ldap3::{
+    LdapError, LdapResult, ResultEntry, Scope, SearchStream,
+    adapters::Adapter,
+    controls::{Control, MakeCritical, RawControl},
+};
+use std::fmt::Debug;
+use tracing::debug;
+
+use crate::{
+    Error,
+    sort::SERVER_SIDE_SORT_REQUEST_OID,
+    vlv::{
+        ListView, VIRTUAL_LIST_VIEW_REQUEST_OID, VIRTUAL_LIST_VIEW_RESPONSE_OID,
+        control::{VirtualListViewRequest, VirtualListViewResponse},
+    },
+};
+
+pub(crate) use crate::vlv::control::VirtualListViewResult;
+
+/// Search adapter for browsing a window of the results.
+///
+/// Needs to come after the Server Side Sort adapter.
+#[derive(Debug, Clone)]
+pub(crate) struct VirtualListView {
+    /// Taken when the search starts.
+    request: Option<VirtualListViewRequest>,
+}
+
+impl VirtualListView {
+    /// Create new adapter instance. The sort of the view is left for the sort adapter.
+    pub fn new(view: &ListView) -> Self {
+        VirtualListView {
+            request: Some(VirtualListViewRequest {
+                before_count: view.before_count,
+                after_count: view.after_count,
+                target: view.target.clone(),
+                context_id: view.context_id.clone(),
+            }),
+        }
+    }
+}
+
+// Not implementing `SoloMarker`, as this doesn't work without sorting.
+
+#[async_trait]
+impl<'a, S, A> Adapter<'a, S, A> for VirtualListView
+where
+    S: AsRef<str> + Clone + Debug + Send + Sync + 'a,
+    A: AsRef<[S]> + Clone + Debug + Send + Sync + 'a,
+{
+    async fn start(
+        &mut self,
+        stream: &mut SearchStream<'a, S, A>,
+        base: &str,
+        scope: Scope,
+        filter: &str,
+        attrs: A,
+    ) -> ldap3::result::Result<()> {
+        let stream_ldap = stream.ldap_handle();
+
+        let has_control = |oid: &str| {
+            stream_ldap
+                .controls
+                .as_ref()
+                .is_some_and(|vec| vec.iter().any(|control| control.ctype == oid))
+        };
+        if has_control(VIRTUAL_LIST_VIEW_REQUEST_OID) {
+            return Err(LdapError::AdapterInit(String::from(
+                "found Virtual List View control in op set already",
+            )));
+        }
+        if !has_control(SERVER_SIDE_SORT_REQUEST_OID) {
+            return Err(LdapError::AdapterInit(String::from(
+                "Virtual List View needs the Server Side Sort control",
+            )));
+        }
+
+        let Some(request) = self.request.take() else {
+            return Err(LdapError::AdapterInit(String::from(
+                "Virtual List View adapter started twice",
+            )));
+        };
+
+        // We want the search to fail if VLV isn't supported.
+        stream_ldap
+            .controls
+            .get_or_insert_default()
+            .push(request.critical().into());
+
+        // Continue the chain.
+        stream.start(base, scope, filter, attrs).await
+    }
+
+    async fn next(
+        &mut self,
+        stream: &mut SearchStream<'a, S, A>,
+    ) -> ldap3::result::Result<Option<ResultEntry>> {
+        // The response control only comes with the final result.
+        stream.next().await
+    }
+
+    async fn finish(&mut self, stream: &mut SearchStream<'a, S, A>) -> LdapResult {
+        // Just logging here
+
+        let result = stream.finish().await;
+
+        match get_response_control(result.ctrls.as_slice()) {
+            Ok(None) => debug!("No Virtual List View control in the final result"),
+            Ok(Some(control)) => debug!("The final Virtual List View control: {control:?}"),
+            Err(error) => debug!("{error}"),
+        };
+
+        result
+    }
+}
+
+/// Get and parse the VLV response control if there is one.
+pub(crate) fn get_response_control(
+    controls: &[Control],
+) -> Result<Option<VirtualListViewResponse>, Error> {
+    controls
+        .iter()
+        // Control type isn't parsed since this control is implemented outside ldap3
+        // so we're just working with the raw values.
+        .map(|Control(_, raw)| raw)
+        .find(|raw| raw.ctype == VIRTUAL_LIST_VIEW_RESPONSE_OID)
+        .map(|RawControl { val, .. }| {
+            val.as_deref()
+                .and_then(VirtualListViewResponse::parse)
+                .ok_or_else(|| {
+                    Error::VirtualListView(String::from(
+                        "The server returned a malformed Virtual List View response",
+                    ))
+                })
+        })
+        .transpose()
+}
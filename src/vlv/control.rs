@@ -0,0 +1,303 @@
+//! The low level control implementation of Virtual List View (VLV)
+//!
+
+use bytes::BytesMut;
+use ldap3::{
+    asn1::{
+        ASNTag, Integer, OctetString, Sequence, Tag, TagClass, Types, parse_tag, parse_uint, write,
+    },
+    controls::{MakeCritical, RawControl},
+};
+
+use crate::vlv::{VIRTUAL_LIST_VIEW_REQUEST_OID, ViewTarget};
+
+/// Request control for VLV.
+///
+/// ```text
+/// VirtualListViewRequest ::= SEQUENCE {
+///     beforeCount    INTEGER (0..maxInt),
+///     afterCount     INTEGER (0..maxInt),
+///     target       CHOICE {
+///         byOffset        [0] SEQUENCE {
+///             offset          INTEGER (1 .. maxInt),
+///             contentCount    INTEGER (0 .. maxInt) },
+///         greaterThanOrEqual [1] AssertionValue },
+///     contextID     OCTET STRING OPTIONAL }
+/// ```
+#[derive(Debug, Clone)]
+pub(crate) struct VirtualListViewRequest {
+    pub before_count: u32,
+    pub after_count: u32,
+    pub target: ViewTarget,
+    pub context_id: Option<Vec<u8>>,
+}
+
+/// May be critical.
+impl MakeCritical for VirtualListViewRequest {}
+
+// Implicit tags
+const BY_OFFSET_TAG: u64 = 0;
+const GREATER_THAN_OR_EQUAL_TAG: u64 = 1;
+
+impl From<VirtualListViewRequest> for RawControl {
+    fn from(value: VirtualListViewRequest) -> Self {
+        let integer = |inner: u32| {
+            Tag::Integer(Integer {
+                inner: inner.into(),
+                ..Default::default()
+            })
+        };
+
+        let target = match value.target {
+            ViewTarget::Offset {
+                offset,
+                content_count,
+            } => Tag::Sequence(Sequence {
+                id: BY_OFFSET_TAG,
+                class: TagClass::Context,
+                inner: vec![integer(offset), integer(content_count)],
+            }),
+            ViewTarget::GreaterThanOrEqual(assertion_value) => Tag::OctetString(OctetString {
+                id: GREATER_THAN_OR_EQUAL_TAG,
+                class: TagClass::Context,
+                inner: assertion_value.into(),
+            }),
+        };
+
+        let iterator = [
+            Some(integer(value.before_count)),
+            Some(integer(value.after_count)),
+            Some(target),
+            value.context_id.map(|context_id| {
+                Tag::OctetString(OctetString {
+                    inner: context_id,
+                    ..Default::default()
+                })
+            }),
+        ]
+        .into_iter()
+        .flatten(); // The Options
+
+        let tagged = Tag::Sequence(Sequence {
+            inner: iterator.collect(),
+            ..Default::default()
+        })
+        .into_structure();
+
+        let mut buffer = BytesMut::new();
+        write::encode_into(&mut buffer, tagged).expect("Encoding should pass");
+
+        RawControl {
+            ctype: VIRTUAL_LIST_VIEW_REQUEST_OID.to_owned(),
+            crit: false,
+            val: Some(buffer.into()),
+        }
+    }
+}
+
+/*******************************
+ *  Then the response control  *
+ *******************************/
+
+/// Response control for VLV.
+///
+/// ```text
+/// VirtualListViewResponse ::= SEQUENCE {
+///     targetPosition    INTEGER (0 .. maxInt),
+///     contentCount      INTEGER (0 .. maxInt),
+///     virtualListViewResult ENUMERATED { ... },
+///     contextID     OCTET STRING OPTIONAL }
+/// ```
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) struct VirtualListViewResponse {
+    pub target_position: u32,
+    pub content_count: u32,
+    pub result: VirtualListViewResult,
+    pub context_id: Option<Vec<u8>>,
+}
+
+/// Potential results to VLV.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub(crate) enum VirtualListViewResult {
+    Success,
+    OperationsError,
+    TimeLimitExceeded,
+    AdminLimitExceeded,
+    InsufficientAccessRights,
+    Busy,
+    UnwillingToPerform,
+    /// The Server Side Sort control was missing from the request.
+    SortControlMissing,
+    /// The offset or the content count was invalid.
+    OffsetRangeError,
+    /// `other` (80), or a code the draft doesn't list.
+    Other(u64),
+}
+
+impl From<u64> for VirtualListViewResult {
+    fn from(value: u64) -> Self {
+        match value {
+            0 => Self::Success,
+            1 => Self::OperationsError,
+            3 => Self::TimeLimitExceeded,
+            11 => Self::AdminLimitExceeded,
+            50 => Self::InsufficientAccessRights,
+            51 => Self::Busy,
+            53 => Self::UnwillingToPerform,
+            60 => Self::SortControlMissing,
+            61 => Self::OffsetRangeError,
+            other => Self::Other(other),
+        }
+    }
+}
+
+impl VirtualListViewResponse {
+    /// Parse the control value.
+    ///
+    /// Returns `None` rather than panicking on a misbehaving server.
+    pub(crate) fn parse(val: &[u8]) -> Option<Self> {
+        let (_, sequence) = parse_tag(val).ok()?;
+        let mut sequence_components = sequence
+            .match_class(TagClass::Universal)?
+            .match_id(Types::Sequence as u64)?
+            .expect_constructed()?
+            .into_iter();
+
+        let mut next_number = |id: Types| {
+            let raw_number = sequence_components
+                .next()?
+                .match_class(TagClass::Universal)?
+                .match_id(id as u64)?
+                .expect_primitive()?;
+
+            let (_, number) = parse_uint(raw_number.as_slice()).ok()?;
+            Some(number)
+        };
+
+        let target_position = next_number(Types::Integer)?;
+        let content_count = next_number(Types::Integer)?;
+        let result = VirtualListViewResult::from(next_number(Types::Enumerated)?);
+
+        let context_id = match sequence_components.next() {
+            Some(tag) => Some(
+                tag.match_class(TagClass::Universal)?
+                    .match_id(Types::OctetString as u64)?
+                    .expect_primitive()?,
+            ),
+            None => None,
+        };
+
+        Some(VirtualListViewResponse {
+            target_position: u32::try_from(target_position).unwrap_or(u32::MAX),
+            content_count: u32::try_from(content_count).unwrap_or(u32::MAX),
+            result,
+            context_id,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn encode_by_offset() {
+        let control: RawControl = VirtualListViewRequest {
+            before_count: 0,
+            after_count: 19,
+            target: ViewTarget::Offset {
+                offset: 1,
+                content_count: 0,
+            },
+            context_id: None,
+        }
+        .into();
+
+        assert_eq!(control.ctype, VIRTUAL_LIST_VIEW_REQUEST_OID);
+        assert_eq!(
+            control.val.as_deref(),
+            Some(
+                [
+                    0x30, 0x0E, 0x02, 0x01, 0x00, 0x02, 0x01, 0x13, 0xA0, 0x06, 0x02, 0x01, 0x01,
+                    0x02, 0x01, 0x00
+                ]
+                .as_slice()
+            )
+        );
+    }
+
+    #[test]
+    fn encode_greater_than_or_equal() {
+        let control: RawControl = VirtualListViewRequest {
+            before_count: 1,
+            after_count: 2,
+            target: ViewTarget::GreaterThanOrEqual(String::from("M")),
+            context_id: Some(vec![0xAB]),
+        }
+        .into();
+
+        assert_eq!(
+            control.val.as_deref(),
+            Some(
+                [
+                    0x30, 0x0C, 0x02, 0x01, 0x01, 0x02, 0x01, 0x02, 0x81, 0x01, b'M', 0x04, 0x01,
+                    0xAB
+                ]
+                .as_slice()
+            )
+        );
+    }
+
+    #[test]
+    fn parse_response() {
+        let value = [
+            0x30, 0x0E, 0x02, 0x01, 0x25, 0x02, 0x02, 0x01, 0x2C, 0x0A, 0x01, 0x00, 0x04, 0x02,
+            0x01, 0x02,
+        ];
+
+        assert_eq!(
+            VirtualListViewResponse::parse(&value),
+            Some(VirtualListViewResponse {
+                target_position: 37,
+                content_count: 300,
+                result: VirtualListViewResult::Success,
+                context_id: Some(vec![0x01, 0x02]),
+            })
+        );
+    }
+
+    #[test]
+    fn parse_error_response() {
+        let value = [
+            0x30, 0x09, 0x02, 0x01, 0x00, 0x02, 0x01, 0x00, 0x0A, 0x01, 0x3D,
+        ];
+
+        let response = VirtualListViewResponse::parse(&value).unwrap();
+        assert_eq!(response.result, VirtualListViewResult::OffsetRangeError);
+        assert_eq!(response.context_id, None);
+    }
+
+    #[test]
+    fn parse_unknown_result() {
+        let value = [
+            0x30, 0x09, 0x02, 0x01, 0x00, 0x02, 0x01, 0x00, 0x0A, 0x01, 0x63,
+        ];
+
+        let response = VirtualListViewResponse::parse(&value).unwrap();
+        assert_eq!(response.result, VirtualListViewResult::Other(99));
+    }
+
+    #[test]
+    fn parse_malformed() {
+        // Not a sequence.
+        assert_eq!(VirtualListViewResponse::parse(&[0x02, 0x01, 0x00]), None);
+        // The result is missing.
+        assert_eq!(
+            VirtualListViewResponse::parse(&[0x30, 0x06, 0x02, 0x01, 0x00, 0x02, 0x01, 0x00]),
+            None
+        );
+        // Truncated.
+        assert_eq!(VirtualListViewResponse::parse(&[0x30, 0x09, 0x02]), None);
+        assert_eq!(VirtualListViewResponse::parse(&[]), None);
+    }
+}
@@ -45,20 +45,25 @@ use rand::RngExt;
 use serde::Deserialize;
 use std::{
     collections::HashSet,
-    num::{NonZero, NonZeroU16},
+    num::{NonZero, NonZeroU16, NonZeroU32},
     ops::DerefMut,
     str::FromStr,
     sync::Once,
+    time::Duration,
 };
+use tokio::runtime::Handle;
 use tracing::Level;
 use tracing_subscriber::fmt::format::FmtSpan;
 use url::Url;
 use uuid::Uuid;
 
 use simple_ldap::{
-    Error, GroupObjectClass, LdapClient, LdapConfig, SimpleDN, SortBy,
+    Authenticated, AuthenticationFailureReason, BindIdentity, BindMethod, BindTemplate,
+    DeleteSubtreeOptions, Error, GroupObjectClass, LdapClient, LdapConfig, ListView, MappingErrors,
+    Page, ReferralPolicy, ResultCode, SearchLimit, SearchOptions, SimpleDN, SortBy, TlsMode,
+    ViewTarget, Window,
     filter::{ContainsFilter, EqFilter},
-    ldap3::{Mod, Scope},
+    ldap3::{DerefAliases, Mod, Scope, controls::RawControl},
 };
 
 pub async fn test_create_record<Client: DerefMut<Target = LdapClient>>(
@@ -585,6 +590,7 @@ pub async fn test_no_record_delete<Client: DerefMut<Target = LdapClient>>(
         .await;
     assert!(result.is_err());
     let er = result.err().unwrap();
+    assert_eq!(er.result_code(), Some(ResultCode::NoSuchObject));
     match er {
         Error::NotFound(_) => Ok(()),
         _ => Err(anyhow!("Unknown error")),
@@ -657,8 +663,10 @@ pub async fn test_get_members<Client: DerefMut<Target = LdapClient>>(
             group_ou.as_str(),
             Scope::Subtree,
             &vec!["cn", "sn", "uid"],
+            MappingErrors::Fail,
         )
-        .await?;
+        .await?
+        .entries;
 
     assert_eq!(users.len(), 2);
     let user_count = users
@@ -670,6 +678,33 @@ pub async fn test_get_members<Client: DerefMut<Target = LdapClient>>(
         .count();
     assert_eq!(user_count, 2);
 
+    // Without the surname the members can't be mapped.
+    let unmappable = client
+        .get_members::<_, _, User>(
+            group_dn.as_str(),
+            group_ou.as_str(),
+            Scope::Subtree,
+            &vec!["cn", "uid"],
+            MappingErrors::Fail,
+        )
+        .await;
+    assert!(
+        matches!(unmappable, Err(Error::Mapping(..))),
+        "{unmappable:?}"
+    );
+
+    let collected = client
+        .get_members::<_, _, User>(
+            group_dn.as_str(),
+            group_ou.as_str(),
+            Scope::Subtree,
+            &vec!["cn", "uid"],
+            MappingErrors::Collect,
+        )
+        .await?;
+    assert!(collected.entries.is_empty());
+    assert_eq!(collected.errors.len(), 2);
+
     Ok(())
 }
 
@@ -716,6 +751,7 @@ pub async fn test_remove_users_from_group<Client: DerefMut<Target = LdapClient>>
     //         group_ou.as_str(),
     //         Scope::Subtree,
     //         &vec!["cn", "sn", "uid"],
+    //         MappingErrors::Fail,
     //     )
     //     .await?;
 
@@ -735,7 +771,8 @@ pub async fn test_associated_groups<Client: DerefMut<Target = LdapClient>>(
         )
         .await?;
 
-    assert_eq!(result.len(), 2);
+    assert_eq!(result.entries.len(), 2);
+    assert_eq!(result.limit, None);
 
     Ok(())
 }
@@ -765,9 +802,17 @@ pub async fn test_authenticate_success<Client: DerefMut<Target = LdapClient>>(
         .authenticate(base, uid.as_str(), password.as_str(), Box::new(filter))
         .await;
 
+    // Authenticating the user mustn't change who the client itself is bound as.
+    let identity = client.who_am_i().await;
+
     client.delete(uid.as_str(), base).await?;
 
     auth_result?;
+    assert_eq!(identity?.as_deref(), Some("dn:cn=manager"));
+    assert_eq!(
+        client.bound_identity(),
+        BindIdentity::Dn(String::from("cn=manager"))
+    );
 
     Ok(())
 }
@@ -800,12 +845,938 @@ pub async fn test_authenticate_wrong_password<Client: DerefMut<Target = LdapClie
     client.delete(uid.as_str(), base).await?;
 
     match auth_result {
+        Err(Error::AuthenticationFailed(failure)) => {
+            assert_eq!(
+                failure.reason(),
+                AuthenticationFailureReason::InvalidCredentials
+            );
+            Ok(())
+        }
+        Err(other) => Err(anyhow!("Unexpected error: {other:?}")),
+        Ok(_) => Err(anyhow!("Authentication succeeded unexpectedly")),
+    }
+}
+
+pub async fn test_compare<Client: DerefMut<Target = LdapClient>>(
+    mut client: Client,
+) -> anyhow::Result<()> {
+    let dn = "uid=f92f4cb2-e821-44a4-bb13-b8ebadf4ecc5,ou=people,dc=example,dc=com";
+
+    assert!(client.compare(dn, "cn", "Sam").await?);
+    assert!(!client.compare(dn, "cn", "SamX").await?);
+
+    let result = client
+        .compare(
+            "uid=4d9b08fe-9a14-4df0-9831-ea9992837f0x,ou=people,dc=example,dc=com",
+            "cn",
+            "Sam",
+        )
+        .await;
+    match result {
+        Err(Error::NotFound(_)) => Ok(()),
+        other => Err(anyhow!("Unexpected result: {other:?}")),
+    }
+}
+
+pub async fn test_move_entry<Client: DerefMut<Target = LdapClient>>(
+    mut client: Client,
+) -> anyhow::Result<()> {
+    let base = "ou=people,dc=example,dc=com";
+    let new_parent = "ou=group,dc=example,dc=com";
+
+    let mut user = create_test_user(&mut client, base, "Move", "Me", None).await?;
+
+    // This is what we are testing.
+    let new_dn = client.move_entry(&user.dn(), new_parent).await?;
+    user.base = new_parent.to_owned();
+
+    assert_eq!(new_dn, SimpleDN::from_str(&user.dn())?);
+    assert_eq!(new_dn.parent(), Some(SimpleDN::from_str(new_parent)?));
+
+    let filter = EqFilter::from("uid".to_string(), user.uid.clone());
+    let found: User = client
+        .search(
+            new_parent,
+            Scope::OneLevel,
+            &filter,
+            vec!["cn", "sn", "uid"],
+        )
+        .await?;
+    assert_eq!(found.dn, new_dn);
+
+    Ok(())
+}
+
+pub async fn test_rename<Client: DerefMut<Target = LdapClient>>(
+    mut client: Client,
+) -> anyhow::Result<()> {
+    let base = "ou=people,dc=example,dc=com";
+
+    let mut user = create_test_user(&mut client, base, "Rename", "Me", None).await?;
+
+    let new_uid = random_uid();
+
+    // This is what we are testing.
+    let new_dn = client
+        .rename(&user.dn(), format!("uid={new_uid}").as_str(), None, true)
+        .await?;
+    assert_eq!(new_dn.rdn(), format!("uid={new_uid}"));
+    user.uid = new_uid;
+
+    assert_eq!(new_dn, SimpleDN::from_str(&user.dn())?);
+
+    Ok(())
+}
+
+pub async fn test_delete_subtree<Client: DerefMut<Target = LdapClient>>(
+    mut client: Client,
+) -> anyhow::Result<()> {
+    // Building a small tree of people under people.
+    let base = "ou=people,dc=example,dc=com";
+    let parent = create_test_user(&mut client, base, "Subtree", "Member", None).await?;
+    let parent_dn = parent.dn();
+    let child = create_test_user(&mut client, &parent_dn, "Subtree", "Member", None).await?;
+    let child_dn = child.dn();
+
+    let dry_run = DeleteSubtreeOptions {
+        dry_run: true,
+        ..Default::default()
+    };
+    let listed = client.delete_subtree(&parent_dn, dry_run).await?;
+    assert_eq!(
+        listed
+            .iter()
+            .map(|dn| SimpleDN::from_str(dn))
+            .collect::<Result<Vec<_>, _>>()?,
+        vec![
+            SimpleDN::from_str(&child_dn)?,
+            SimpleDN::from_str(&parent_dn)?
+        ]
+    );
+
+    // This is what we are testing.
+    let deleted = client
+        .delete_subtree(&parent_dn, DeleteSubtreeOptions::default())
+        .await?;
+    // Either just the root with Tree Delete, or everything deleted one by one.
+    assert_eq!(
+        deleted
+            .last()
+            .map(|dn| SimpleDN::from_str(dn))
+            .transpose()?,
+        Some(SimpleDN::from_str(&parent_dn)?)
+    );
+
+    let filter = EqFilter::from("uid".to_string(), parent.uid.clone());
+    let result: Result<User, Error> = client
+        .search(base, Scope::OneLevel, &filter, vec!["cn", "sn", "uid"])
+        .await;
+    match result {
+        Err(Error::NotFound(_)) => Ok(()),
+        other => Err(anyhow!("Subtree root still exists: {other:?}")),
+    }
+}
+
+pub async fn test_change_and_reset_password<Client: DerefMut<Target = LdapClient>>(
+    mut client: Client,
+) -> anyhow::Result<()> {
+    let password = format!("secret-{}", random_uid());
+    let base = "ou=people,dc=example,dc=com";
+
+    let user = create_test_user(
+        &mut client,
+        base,
+        "Password",
+        "Changer",
+        Some(password.as_str()),
+    )
+    .await?;
+
+    // These are what we are testing.
+    client
+        .change_password(&user.dn(), Some(password.as_str()), "a-whole-new-secret")
+        .await?;
+    let generated = client.reset_password(&user.dn()).await?;
+    assert!(!generated.is_empty());
+
+    Ok(())
+}
+
+pub async fn test_who_am_i<Client: DerefMut<Target = LdapClient>>(
+    mut client: Client,
+) -> anyhow::Result<()> {
+    let identity = client.who_am_i().await?;
+
+    assert_eq!(identity.as_deref(), Some("dn:cn=manager"));
+    assert_eq!(
+        client.bound_identity(),
+        BindIdentity::Dn(String::from("cn=manager"))
+    );
+
+    Ok(())
+}
+
+pub async fn test_authenticate_empty_password<Client: DerefMut<Target = LdapClient>>(
+    mut client: Client,
+) -> anyhow::Result<()> {
+    // An existing user, which must not be let in without a password.
+    let filter = EqFilter::from(
+        "uid".to_string(),
+        "f92f4cb2-e821-44a4-bb13-b8ebadf4ecc5".to_string(),
+    );
+    let auth_result = client
+        .authenticate("ou=people,dc=example,dc=com", "Sam", "", Box::new(filter))
+        .await;
+
+    match auth_result {
+        Err(Error::AuthenticationFailed(failure)) => {
+            assert_eq!(failure.reason(), AuthenticationFailureReason::InvalidInput);
+            Ok(())
+        }
+        Err(other) => Err(anyhow!("Unexpected error: {other:?}")),
+        Ok(_) => Err(anyhow!("Authentication succeeded unexpectedly")),
+    }
+}
+
+pub async fn test_authenticate_and_fetch<Client: DerefMut<Target = LdapClient>>(
+    mut client: Client,
+) -> anyhow::Result<()> {
+    let password = format!("secret-{}", random_uid());
+    let base = "ou=people,dc=example,dc=com";
+
+    let user = create_test_user(
+        &mut client,
+        base,
+        "Fetch",
+        "Tester",
+        Some(password.as_str()),
+    )
+    .await?;
+
+    let filter = EqFilter::from("uid".to_string(), user.uid.clone());
+    let attributes = vec!["uid", "cn", "sn"];
+    let Authenticated {
+        dn,
+        entry,
+        password_policy,
+    } = client
+        .authenticate_and_fetch::<_, _, _, User>(
+            "dc=example,dc=com",
+            &filter,
+            password.as_str(),
+            &attributes,
+        )
+        .await?;
+    assert_eq!(dn.to_string(), user.dn());
+    assert_eq!(entry.dn, dn);
+    assert_eq!(entry.uid, user.uid);
+    assert_eq!(entry.cn, "Fetch");
+    // A fresh password has nothing wrong with it, whether or not the server has a policy.
+    assert_eq!(password_policy.error, None);
+
+    // The search options would leave out the values.
+    let Authenticated { entry, .. } = client
+        .with_search_options(SearchOptions {
+            types_only: true,
+            ..SearchOptions::default()
+        })
+        .authenticate_and_fetch::<_, _, _, User>(
+            "dc=example,dc=com",
+            &filter,
+            password.as_str(),
+            &attributes,
+        )
+        .await?;
+    assert_eq!(entry.cn, "Fetch");
+
+    let wrong_password = client
+        .authenticate_and_fetch::<_, _, _, User>(
+            "dc=example,dc=com",
+            &filter,
+            "definitely-wrong",
+            &attributes,
+        )
+        .await;
+    match wrong_password {
         Err(Error::AuthenticationFailed(_)) => Ok(()),
         Err(other) => Err(anyhow!("Unexpected error: {other:?}")),
         Ok(_) => Err(anyhow!("Authentication succeeded unexpectedly")),
     }
 }
 
+pub async fn test_authenticate_direct<Client: DerefMut<Target = LdapClient>>(
+    mut client: Client,
+) -> anyhow::Result<()> {
+    let password = format!("secret-{}", random_uid());
+    let base = "ou=people,dc=example,dc=com";
+
+    let user = create_test_user(
+        &mut client,
+        base,
+        "Direct",
+        "Tester",
+        Some(password.as_str()),
+    )
+    .await?;
+
+    let template = BindTemplate::Dn(format!("uid={{user}},{base}"));
+    client
+        .authenticate_direct(&template, user.uid.as_str(), password.as_str())
+        .await?;
+
+    let wrong_password = client
+        .authenticate_direct(&template, user.uid.as_str(), "definitely-wrong")
+        .await;
+    assert!(matches!(
+        wrong_password,
+        Err(Error::AuthenticationFailed(_))
+    ));
+
+    // Trying to bind as the manager by injecting into the template.
+    let injection = client
+        .authenticate_direct(&template, "x,cn=manager", "password")
+        .await;
+    assert!(matches!(injection, Err(Error::AuthenticationFailed(_))));
+
+    Ok(())
+}
+
+pub async fn test_operations_with_timeout<Client: DerefMut<Target = LdapClient>>(
+    client: Client,
+) -> anyhow::Result<()> {
+    // Generous enough to never fire, so this checks that the timeouts don't get in the way.
+    let mut client = client.with_timeout(Duration::from_secs(30));
+
+    let name_filter = EqFilter::from("cn".to_string(), "Sam".to_string());
+    let user: User = client
+        .search(
+            "ou=people,dc=example,dc=com",
+            Scope::OneLevel,
+            &name_filter,
+            vec!["cn", "sn", "uid"],
+        )
+        .await?;
+    assert_eq!(user.cn, "Sam");
+
+    // Paging starts a new search for each page, each getting the timeout.
+    let name_filter = ContainsFilter::from("cn".to_string(), "J".to_string());
+    let count = client
+        .streaming_search(
+            "ou=people,dc=example,dc=com",
+            Scope::OneLevel,
+            &name_filter,
+            &vec!["cn", "sn", "uid"],
+            Some(PAGE_SIZE),
+            Vec::new(),
+        )
+        .await?
+        .and_then(async |record| record.to_record())
+        .try_fold(0, async |sum, _: User| Ok(sum + 1))
+        .await?;
+    assert_eq!(count, 3);
+
+    Ok(())
+}
+
+pub async fn test_search_size_limit<Client: DerefMut<Target = LdapClient>>(
+    client: Client,
+) -> anyhow::Result<()> {
+    let mut client = client.with_search_options(SearchOptions {
+        size_limit: NonZeroU32::new(1),
+        ..SearchOptions::default()
+    });
+
+    // There are three of these.
+    let name_filter = ContainsFilter::from("cn".to_string(), "J".to_string());
+    let results: Vec<_> = client
+        .streaming_search(
+            "ou=people,dc=example,dc=com",
+            Scope::OneLevel,
+            &name_filter,
+            &vec!["cn", "sn", "uid"],
+            None,
+            Vec::new(),
+        )
+        .await?
+        .collect()
+        .await;
+
+    match <[_; 2]>::try_from(results) {
+        Ok([Ok(record), Err(Error::LimitExceeded(_, SearchLimit::Size))]) => {
+            let user: User = record.to_record()?;
+            assert!(user.cn.contains('J'));
+        }
+        _ => return Err(anyhow!("Expected one entry and then the size limit")),
+    }
+
+    // A single result search fits the limit.
+    let name_filter = EqFilter::from("cn".to_string(), "Sam".to_string());
+    let user: User = client
+        .search(
+            "ou=people,dc=example,dc=com",
+            Scope::OneLevel,
+            &name_filter,
+            vec!["cn", "sn", "uid"],
+        )
+        .await?;
+    assert_eq!(user.cn, "Sam");
+
+    // Collecting all the results returns the entries before the limit.
+    let name_filter = ContainsFilter::from("cn".to_string(), "J".to_string());
+    let users = client
+        .search_all::<_, _, _, User>(
+            "ou=people,dc=example,dc=com",
+            Scope::OneLevel,
+            &name_filter,
+            vec!["cn", "sn", "uid"],
+            None,
+            Vec::new(),
+            MappingErrors::Fail,
+        )
+        .await?;
+    assert_eq!(users.entries.len(), 1);
+    assert!(users.errors.is_empty());
+    assert_eq!(users.limit, Some(SearchLimit::Size));
+
+    // This user has two groups.
+    let groups = client
+        .get_associated_groups(
+            "ou=group,dc=example,dc=com",
+            "uid=e219fbc0-6df5-4bc3-a6ee-986843bb157e,ou=people,dc=example,dc=com",
+            GroupObjectClass::default(),
+        )
+        .await?;
+    assert_eq!(groups.entries.len(), 1);
+    assert_eq!(groups.limit, Some(SearchLimit::Size));
+
+    Ok(())
+}
+
+pub async fn test_search_all<Client: DerefMut<Target = LdapClient>>(
+    mut client: Client,
+) -> anyhow::Result<()> {
+    let name_filter = ContainsFilter::from("cn".to_string(), "J".to_string());
+    let sort = vec![SortBy {
+        attribute: "cn".to_string(),
+        reverse: false,
+    }];
+
+    let users: Vec<User> = client
+        .search_all(
+            "ou=people,dc=example,dc=com",
+            Scope::OneLevel,
+            &name_filter,
+            vec!["cn", "sn", "uid"],
+            Some(PAGE_SIZE),
+            sort,
+            MappingErrors::Fail,
+        )
+        .await?
+        .entries;
+    assert_eq!(users.len(), 3);
+    assert!(users.is_sorted_by_key(|user| user.cn.clone()));
+
+    // No uid requested, so none of these deserialize.
+    let result = client
+        .search_all::<_, _, _, User>(
+            "ou=people,dc=example,dc=com",
+            Scope::OneLevel,
+            &name_filter,
+            vec!["cn", "sn"],
+            None,
+            Vec::new(),
+            MappingErrors::Fail,
+        )
+        .await;
+    assert!(matches!(result, Err(Error::Mapping(..))));
+
+    let collected = client
+        .search_all::<_, _, _, User>(
+            "ou=people,dc=example,dc=com",
+            Scope::OneLevel,
+            &name_filter,
+            vec!["cn", "sn"],
+            None,
+            Vec::new(),
+            MappingErrors::Collect,
+        )
+        .await?;
+    assert!(collected.entries.is_empty());
+    assert_eq!(collected.errors.len(), 3);
+
+    // Nothing matching is not an error.
+    let no_match = EqFilter::from("cn".to_string(), append_random_id("nobody"));
+    let nobody = client
+        .search_all::<_, _, _, User>(
+            "ou=people,dc=example,dc=com",
+            Scope::OneLevel,
+            &no_match,
+            vec!["cn", "sn", "uid"],
+            None,
+            Vec::new(),
+            MappingErrors::Fail,
+        )
+        .await?;
+    assert!(nobody.entries.is_empty());
+
+    Ok(())
+}
+
+pub async fn test_find_one_and_get<Client: DerefMut<Target = LdapClient>>(
+    mut client: Client,
+) -> anyhow::Result<()> {
+    let name_filter = EqFilter::from("cn".to_string(), "Sam".to_string());
+    let user: Option<User> = client
+        .find_one(
+            "ou=people,dc=example,dc=com",
+            Scope::OneLevel,
+            &name_filter,
+            vec!["cn", "sn", "uid"],
+        )
+        .await?;
+    assert_eq!(user.map(|user| user.sn).as_deref(), Some("Smith"));
+
+    let no_match = EqFilter::from("cn".to_string(), append_random_id("nobody"));
+    let nobody: Option<User> = client
+        .find_one(
+            "ou=people,dc=example,dc=com",
+            Scope::OneLevel,
+            &no_match,
+            vec!["cn", "sn", "uid"],
+        )
+        .await?;
+    assert!(nobody.is_none());
+
+    // A missing base is absent too.
+    let nowhere: Option<User> = client
+        .find_one(
+            "ou=nowhere,dc=example,dc=com",
+            Scope::OneLevel,
+            &name_filter,
+            vec!["cn", "sn", "uid"],
+        )
+        .await?;
+    assert!(nowhere.is_none());
+
+    // Still an error.
+    let ambiguous = ContainsFilter::from("cn".to_string(), "J".to_string());
+    let result = client
+        .find_one::<_, _, _, User>(
+            "ou=people,dc=example,dc=com",
+            Scope::OneLevel,
+            &ambiguous,
+            vec!["cn", "sn", "uid"],
+        )
+        .await;
+    assert!(matches!(result, Err(Error::MultipleResults(_))));
+
+    let dn = "uid=f92f4cb2-e821-44a4-bb13-b8ebadf4ecc5,ou=people,dc=example,dc=com";
+    let user: Option<User> = client.get(dn, vec!["cn", "sn", "uid"]).await?;
+    let user = user.ok_or(anyhow!("Sam should be there"))?;
+    assert_eq!(user.cn, "Sam");
+    assert_eq!(user.dn, SimpleDN::from_str(dn)?);
+
+    let missing = format!("uid={},ou=people,dc=example,dc=com", Uuid::new_v4());
+    let nobody: Option<User> = client.get(&missing, vec!["cn", "sn", "uid"]).await?;
+    assert!(nobody.is_none());
+
+    Ok(())
+}
+
+pub async fn test_streaming_search_as<Client: DerefMut<Target = LdapClient>>(
+    mut client: Client,
+) -> anyhow::Result<()> {
+    let name_filter = ContainsFilter::from("cn".to_string(), "J".to_string());
+    let sort = vec![SortBy {
+        attribute: "cn".to_string(),
+        reverse: true,
+    }];
+
+    let users: Vec<User> = client
+        .streaming_search_as(
+            "ou=people,dc=example,dc=com",
+            Scope::OneLevel,
+            &name_filter,
+            vec!["cn", "sn", "uid"],
+            Some(PAGE_SIZE),
+            sort,
+        )
+        .await?
+        .try_collect()
+        .await?;
+    assert_eq!(users.len(), 3);
+    assert!(users.is_sorted_by_key(|user| std::cmp::Reverse(user.cn.clone())));
+
+    // Without uid these fail to map, and the errors tell which entry it was.
+    let errors: Vec<Result<User, Error>> = client
+        .streaming_search_as(
+            "ou=people,dc=example,dc=com",
+            Scope::OneLevel,
+            &name_filter,
+            vec!["cn", "sn"],
+            None,
+            Vec::new(),
+        )
+        .await?
+        .collect()
+        .await;
+    assert_eq!(errors.len(), 3);
+    for error in errors {
+        match error {
+            Err(Error::Mapping(_, Some(dn))) => {
+                assert!(dn.ends_with(",ou=people,dc=example,dc=com"))
+            }
+            _ => return Err(anyhow!("Expected a mapping error")),
+        }
+    }
+
+    Ok(())
+}
+
+pub async fn test_search_with_referral_policies<Client: DerefMut<Target = LdapClient>>(
+    client: Client,
+) -> anyhow::Result<()> {
+    // There are no referrals among the people, so the policies shouldn't change anything.
+    let policies = [
+        ReferralPolicy::Ignore,
+        ReferralPolicy::Surface,
+        ReferralPolicy::Chase {
+            hop_limit: NonZero::new(2).unwrap(),
+            allow_plaintext: true,
+        },
+    ];
+    for referrals in policies {
+        let mut client = client.with_search_options(SearchOptions {
+            referrals,
+            ..SearchOptions::default()
+        });
+
+        let name_filter = ContainsFilter::from("cn".to_string(), "J".to_string());
+        let users: Vec<User> = client
+            .streaming_search_as(
+                "ou=people,dc=example,dc=com",
+                Scope::OneLevel,
+                &name_filter,
+                vec!["cn", "sn", "uid"],
+                Some(PAGE_SIZE),
+                Vec::new(),
+            )
+            .await?
+            .try_collect()
+            .await?;
+        assert_eq!(users.len(), 3, "With {referrals:?}");
+    }
+
+    // Two subtrees are elsewhere, each in its own reference.
+    let referred = async |referrals| -> anyhow::Result<Vec<Result<User, Error>>> {
+        let results = client
+            .with_search_options(SearchOptions {
+                referrals,
+                ..SearchOptions::default()
+            })
+            .streaming_search_as(
+                "ou=referrals,dc=example,dc=com",
+                Scope::OneLevel,
+                &EqFilter::from("objectClass".to_string(), "person".to_string()),
+                vec!["cn", "sn", "uid"],
+                Some(PAGE_SIZE),
+                Vec::new(),
+            )
+            .await?
+            .collect()
+            .await;
+        Ok(results)
+    };
+
+    assert!(referred(ReferralPolicy::Ignore).await?.is_empty());
+
+    let surfaced = referred(ReferralPolicy::Surface).await?;
+    let urls: HashSet<_> = surfaced
+        .iter()
+        .map(|result| match result {
+            Err(Error::Referral(_, urls)) => urls.iter().exactly_one().ok(),
+            _ => None,
+        })
+        .collect::<Option<_>>()
+        .ok_or_else(|| anyhow!("Expected only single URL referrals"))?;
+    assert_eq!(surfaced.len(), 2);
+    assert_eq!(urls.len(), 2);
+
+    // Collecting is only for the mapping errors.
+    let collected = client
+        .with_search_options(SearchOptions {
+            referrals: ReferralPolicy::Surface,
+            ..SearchOptions::default()
+        })
+        .search_all::<_, _, _, User>(
+            "ou=referrals,dc=example,dc=com",
+            Scope::OneLevel,
+            &EqFilter::from("objectClass".to_string(), "person".to_string()),
+            vec!["cn", "sn", "uid"],
+            Some(PAGE_SIZE),
+            Vec::new(),
+            MappingErrors::Collect,
+        )
+        .await;
+    assert!(
+        matches!(collected, Err(Error::Referral(..))),
+        "{collected:?}"
+    );
+
+    // Both are chased, and refused as the test server is plaintext.
+    let chased = referred(ReferralPolicy::Chase {
+        hop_limit: NonZero::new(1).unwrap(),
+        allow_plaintext: false,
+    })
+    .await?;
+    assert_eq!(chased.len(), 2);
+    assert!(
+        chased
+            .iter()
+            .all(|result| matches!(result, Err(Error::Config(_))))
+    );
+
+    Ok(())
+}
+
+pub async fn test_search_options<Client: DerefMut<Target = LdapClient>>(
+    client: Client,
+) -> anyhow::Result<()> {
+    let mut client = client.with_search_options(SearchOptions {
+        types_only: true,
+        deref_aliases: DerefAliases::Always,
+        // ManageDsaIT, harmless for a search.
+        controls: vec![RawControl {
+            ctype: String::from("2.16.840.1.113730.3.4.2"),
+            crit: false,
+            val: None,
+        }],
+        ..SearchOptions::default()
+    });
+
+    let name_filter = EqFilter::from("cn".to_string(), "Sam".to_string());
+    let records: Vec<_> = client
+        .streaming_search(
+            "ou=people,dc=example,dc=com",
+            Scope::OneLevel,
+            &name_filter,
+            vec!["cn", "sn", "uid"],
+            None,
+            Vec::new(),
+        )
+        .await?
+        .try_collect()
+        .await?;
+    let [record] = records.as_slice() else {
+        return Err(anyhow!("Expected exactly one record"));
+    };
+
+    let names: HashSet<_> = record.attribute_names().collect();
+    assert_eq!(names, HashSet::from(["cn", "sn", "uid"]));
+
+    // No values to deserialize.
+    let result: Result<User, Error> = client
+        .search(
+            "ou=people,dc=example,dc=com",
+            Scope::OneLevel,
+            &name_filter,
+            vec!["cn", "sn", "uid"],
+        )
+        .await;
+    assert!(matches!(result, Err(Error::Mapping(..))));
+
+    // The alias itself isn't a person, only the entry it points to.
+    let person_filter = EqFilter::from("objectClass".to_string(), "person".to_string());
+    let people_by_alias = async |deref_aliases| {
+        client
+            .with_search_options(SearchOptions {
+                deref_aliases,
+                ..SearchOptions::default()
+            })
+            .search_all::<_, _, _, User>(
+                "ou=aliases,dc=example,dc=com",
+                Scope::OneLevel,
+                &person_filter,
+                vec!["cn", "sn", "uid"],
+                None,
+                Vec::new(),
+                MappingErrors::Fail,
+            )
+            .await
+    };
+
+    let not_dereferenced = people_by_alias(DerefAliases::Never).await?;
+    assert!(not_dereferenced.entries.is_empty());
+
+    let dereferenced = people_by_alias(DerefAliases::Always).await?;
+    let [user] = dereferenced.entries.as_slice() else {
+        return Err(anyhow!("Expected the aliased entry"));
+    };
+    assert_eq!(user.cn, "Sam");
+
+    Ok(())
+}
+
+pub async fn test_browse<Client: DerefMut<Target = LdapClient>>(
+    mut client: Client,
+) -> anyhow::Result<()> {
+    let person_filter = EqFilter::from("objectClass".to_string(), "person".to_string());
+    let sort_by = vec![SortBy {
+        attribute: "cn".to_string(),
+        reverse: false,
+    }];
+
+    let window: Window<User> = client
+        .browse(
+            "ou=people,dc=example,dc=com",
+            Scope::OneLevel,
+            &person_filter,
+            vec!["cn", "sn", "uid"],
+            ListView {
+                sort_by: sort_by.clone(),
+                target: ViewTarget::Offset {
+                    offset: 1,
+                    content_count: 0,
+                },
+                before_count: 0,
+                after_count: 1,
+                context_id: None,
+            },
+        )
+        .await?;
+
+    assert_eq!(window.entries.len(), 2);
+    assert_eq!(window.target_position, 1);
+    assert!(window.content_count >= 3);
+    assert!(window.entries[0].cn <= window.entries[1].cn);
+
+    let window: Window<User> = client
+        .browse(
+            "ou=people,dc=example,dc=com",
+            Scope::OneLevel,
+            &person_filter,
+            vec!["cn", "sn", "uid"],
+            ListView {
+                sort_by,
+                target: ViewTarget::GreaterThanOrEqual("J".to_string()),
+                before_count: 0,
+                after_count: 0,
+                context_id: window.context_id,
+            },
+        )
+        .await?;
+
+    let [first] = window.entries.as_slice() else {
+        return Err(anyhow!("Expected exactly one record"));
+    };
+    assert!(first.cn.as_str() >= "J");
+
+    // The view needs a sort.
+    let result: Result<Window<User>, Error> = client
+        .browse(
+            "ou=people,dc=example,dc=com",
+            Scope::OneLevel,
+            &person_filter,
+            vec!["cn"],
+            ListView {
+                sort_by: Vec::new(),
+                target: ViewTarget::Offset {
+                    offset: 1,
+                    content_count: 0,
+                },
+                before_count: 0,
+                after_count: 0,
+                context_id: None,
+            },
+        )
+        .await;
+    assert!(matches!(result, Err(Error::VirtualListView(_))));
+
+    Ok(())
+}
+
+pub async fn test_search_page<Client: DerefMut<Target = LdapClient>>(
+    mut client: Client,
+) -> anyhow::Result<()> {
+    let person_filter = EqFilter::from("objectClass".to_string(), "person".to_string());
+    let page_size = NonZeroU16::new(2).unwrap();
+    let sort_by = vec![SortBy {
+        attribute: "cn".to_string(),
+        reverse: false,
+    }];
+
+    let all: Vec<User> = client
+        .search_all(
+            "ou=people,dc=example,dc=com",
+            Scope::OneLevel,
+            &person_filter,
+            vec!["cn", "sn", "uid"],
+            None,
+            sort_by.clone(),
+            MappingErrors::Fail,
+        )
+        .await?
+        .entries;
+
+    // The pages have to come from the same connection, so using the same client for all of them.
+    let mut paged = Vec::new();
+    let mut cookie = None;
+    loop {
+        let page: Page<User> = client
+            .search_page(
+                "ou=people,dc=example,dc=com",
+                Scope::OneLevel,
+                &person_filter,
+                vec!["cn", "sn", "uid"],
+                page_size,
+                sort_by.clone(),
+                cookie,
+            )
+            .await?;
+        assert!(page.items.len() <= 2);
+
+        paged.extend(page.items);
+        cookie = page.next_cookie;
+        if cookie.is_none() {
+            break;
+        }
+    }
+
+    assert!(all.len() > 2);
+    assert_eq!(
+        paged.iter().map(|user| &user.dn).collect_vec(),
+        all.iter().map(|user| &user.dn).collect_vec()
+    );
+
+    // Leaving after the first page.
+    let first: Page<User> = client
+        .search_page(
+            "ou=people,dc=example,dc=com",
+            Scope::OneLevel,
+            &person_filter,
+            vec!["cn", "sn", "uid"],
+            page_size,
+            sort_by.clone(),
+            None,
+        )
+        .await?;
+    let cookie = first
+        .next_cookie
+        .ok_or_else(|| anyhow!("Expected more pages"))?;
+    client
+        .end_paged_search(
+            "ou=people,dc=example,dc=com",
+            Scope::OneLevel,
+            &person_filter,
+            vec!["cn", "sn", "uid"],
+            sort_by,
+            cookie,
+        )
+        .await?;
+
+    Ok(())
+}
+
 /***************
  *  Utilities  *
  ***************/
@@ -830,13 +1801,85 @@ fn random_uid() -> String {
         .to_owned()
 }
 
+/// A person created for a single test.
+///
+/// The entry is deleted when this is dropped, so that it doesn't leak into the other tests
+/// even if the test fails. Update `uid` and `base` if the test moves the entry.
+pub struct TestUser {
+    pub uid: String,
+    pub base: String,
+    /// Shares the connection of the test's client.
+    client: LdapClient,
+}
+
+impl TestUser {
+    pub fn dn(&self) -> String {
+        format!("uid={},{}", self.uid, self.base)
+    }
+}
+
+impl Drop for TestUser {
+    fn drop(&mut self) {
+        // The tests run in a multi threaded runtime, so we can block here.
+        let Ok(runtime) = Handle::try_current() else {
+            eprintln!("Not deleting the test user {}, no runtime", self.dn());
+            return;
+        };
+        let deleted = tokio::task::block_in_place(|| {
+            runtime.block_on(self.client.delete(&self.uid, &self.base))
+        });
+        match deleted {
+            // Some tests delete the user themselves.
+            Ok(()) | Err(Error::NotFound(_)) => (),
+            Err(error) => eprintln!("Failed to delete the test user {}: {error}", self.dn()),
+        }
+    }
+}
+
+/// Create a person with a random uid for a single test.
+async fn create_test_user(
+    client: &mut LdapClient,
+    base: &str,
+    cn: &str,
+    sn: &str,
+    password: Option<&str>,
+) -> anyhow::Result<TestUser> {
+    let uid = random_uid();
+    let mut data = vec![
+        (
+            "objectClass",
+            HashSet::from(["organizationalPerson", "inetorgperson", "top", "person"]),
+        ),
+        ("uid", HashSet::from([uid.as_str()])),
+        ("cn", HashSet::from([cn])),
+        ("sn", HashSet::from([sn])),
+    ];
+    if let Some(password) = password {
+        data.push(("userPassword", HashSet::from([password])));
+    }
+
+    client.create(uid.as_str(), base, data).await?;
+
+    Ok(TestUser {
+        uid,
+        base: base.to_owned(),
+        client: client.clone(),
+    })
+}
+
 /// Get ldap configuration for connecting to the test server.
 pub fn ldap_config() -> anyhow::Result<LdapConfig> {
     let config = LdapConfig {
-        bind_dn: String::from("cn=manager"),
-        bind_password: String::from("password"),
+        bind_dn: Some(String::from("cn=manager")),
+        bind_password: Some(String::from("password")),
         ldap_url: Url::parse("ldap://localhost:1389/dc=example,dc=com")?,
+        tls: TlsMode::None,
+        tls_options: None,
+        bind_method: BindMethod::Simple,
         dn_attribute: None,
+        retry: None,
+        operation_timeout: None,
+        password_policy: false,
         connection_settings: None,
     };
 
@@ -3,8 +3,22 @@
 //! this is just a thin wrapper around it.
 mod client_test_cases;
 
-use client_test_cases::ldap_config;
-use simple_ldap::LdapClient;
+use std::{
+    collections::HashSet,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use client_test_cases::{User, ldap_config};
+use futures::TryStreamExt;
+use simple_ldap::{LdapClient, RetryPolicy, filter::EqFilter, ldap3::Scope};
+use tokio::{
+    io::copy_bidirectional,
+    net::{TcpListener, TcpStream},
+    task::AbortHandle,
+};
+use url::Url;
+use uuid::Uuid;
 
 /// Get a normal LDAP client to run integration tests with.
 ///
@@ -154,3 +168,220 @@ async fn test_authenticate_wrong_password() -> anyhow::Result<()> {
     let client = get_test_client().await?;
     client_test_cases::test_authenticate_wrong_password(Box::new(client)).await
 }
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_compare() -> anyhow::Result<()> {
+    let client = get_test_client().await?;
+    client_test_cases::test_compare(Box::new(client)).await
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_move_entry() -> anyhow::Result<()> {
+    let client = get_test_client().await?;
+    client_test_cases::test_move_entry(Box::new(client)).await
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_rename() -> anyhow::Result<()> {
+    let client = get_test_client().await?;
+    client_test_cases::test_rename(Box::new(client)).await
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_delete_subtree() -> anyhow::Result<()> {
+    let client = get_test_client().await?;
+    client_test_cases::test_delete_subtree(Box::new(client)).await
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_change_and_reset_password() -> anyhow::Result<()> {
+    let client = get_test_client().await?;
+    client_test_cases::test_change_and_reset_password(Box::new(client)).await
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_who_am_i() -> anyhow::Result<()> {
+    let client = get_test_client().await?;
+    client_test_cases::test_who_am_i(Box::new(client)).await
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_authenticate_empty_password() -> anyhow::Result<()> {
+    let client = get_test_client().await?;
+    client_test_cases::test_authenticate_empty_password(Box::new(client)).await
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_authenticate_and_fetch() -> anyhow::Result<()> {
+    let client = get_test_client().await?;
+    client_test_cases::test_authenticate_and_fetch(Box::new(client)).await
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_authenticate_direct() -> anyhow::Result<()> {
+    let client = get_test_client().await?;
+    client_test_cases::test_authenticate_direct(Box::new(client)).await
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_operations_with_timeout() -> anyhow::Result<()> {
+    let client = get_test_client().await?;
+    client_test_cases::test_operations_with_timeout(Box::new(client)).await
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_search_size_limit() -> anyhow::Result<()> {
+    let client = get_test_client().await?;
+    client_test_cases::test_search_size_limit(Box::new(client)).await
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_search_all() -> anyhow::Result<()> {
+    let client = get_test_client().await?;
+    client_test_cases::test_search_all(Box::new(client)).await
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_find_one_and_get() -> anyhow::Result<()> {
+    let client = get_test_client().await?;
+    client_test_cases::test_find_one_and_get(Box::new(client)).await
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_streaming_search_as() -> anyhow::Result<()> {
+    let client = get_test_client().await?;
+    client_test_cases::test_streaming_search_as(Box::new(client)).await
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_search_with_referral_policies() -> anyhow::Result<()> {
+    let client = get_test_client().await?;
+    client_test_cases::test_search_with_referral_policies(Box::new(client)).await
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_search_options() -> anyhow::Result<()> {
+    let client = get_test_client().await?;
+    client_test_cases::test_search_options(Box::new(client)).await
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_browse() -> anyhow::Result<()> {
+    let client = get_test_client().await?;
+    client_test_cases::test_browse(Box::new(client)).await
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_search_page() -> anyhow::Result<()> {
+    let client = get_test_client().await?;
+    client_test_cases::test_search_page(Box::new(client)).await
+}
+
+/// A lost connection is replaced, so that the client keeps working.
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_recover_lost_connection() -> anyhow::Result<()> {
+    let proxy = Proxy::start().await?;
+    let mut ldap_config = ldap_config()?;
+    ldap_config.ldap_url = Url::parse(&format!("ldap://{}/dc=example,dc=com", proxy.address))?;
+    ldap_config.retry = Some(RetryPolicy::default());
+    let mut client = LdapClient::new(ldap_config).await?;
+
+    let name_filter = EqFilter::from("cn".to_string(), "Sam".to_string());
+    let attributes = vec!["cn", "sn", "uid"];
+
+    // Searches are retried on a new connection.
+    proxy.drop_connections().await;
+    let user: User = client
+        .search(
+            "ou=people,dc=example,dc=com",
+            Scope::OneLevel,
+            &name_filter,
+            &attributes,
+        )
+        .await?;
+    assert_eq!(user.cn, "Sam");
+
+    proxy.drop_connections().await;
+    let users: Vec<User> = client
+        .streaming_search_as(
+            "ou=people,dc=example,dc=com",
+            Scope::OneLevel,
+            &name_filter,
+            &attributes,
+            None,
+            Vec::new(),
+        )
+        .await?
+        .try_collect()
+        .await?;
+    assert_eq!(users.len(), 1);
+
+    // Writes aren't repeated, but the next operation gets a new connection.
+    let uid = format!("recover-{}", Uuid::new_v4());
+    let data = || {
+        vec![
+            (
+                "objectClass",
+                HashSet::from(["organizationalPerson", "inetorgperson", "top", "person"]),
+            ),
+            ("uid", HashSet::from([uid.as_str()])),
+            ("cn", HashSet::from(["Recovered"])),
+            ("sn", HashSet::from(["Connection"])),
+        ]
+    };
+    proxy.drop_connections().await;
+    let lost = client
+        .create(&uid, "ou=people,dc=example,dc=com", data())
+        .await
+        .expect_err("The connection was dropped");
+    assert!(lost.is_connection_error(), "{lost:?}");
+
+    client
+        .create(&uid, "ou=people,dc=example,dc=com", data())
+        .await?;
+    client.delete(&uid, "ou=people,dc=example,dc=com").await?;
+
+    Ok(())
+}
+
+/// A TCP proxy in front of the test server, for cutting the connections.
+struct Proxy {
+    address: String,
+    connections: Arc<Mutex<Vec<AbortHandle>>>,
+}
+
+impl Proxy {
+    async fn start() -> anyhow::Result<Self> {
+        let listener = TcpListener::bind("127.0.0.1:0").await?;
+        let address = listener.local_addr()?.to_string();
+        let connections = Arc::new(Mutex::new(Vec::new()));
+
+        let accepted = Arc::clone(&connections);
+        tokio::spawn(async move {
+            while let Ok((mut client, _)) = listener.accept().await {
+                let connection = tokio::spawn(async move {
+                    let mut server = TcpStream::connect("localhost:1389").await?;
+                    copy_bidirectional(&mut client, &mut server).await
+                });
+                accepted
+                    .lock()
+                    .expect("Not poisoned")
+                    .push(connection.abort_handle());
+            }
+        });
+
+        Ok(Self {
+            address,
+            connections,
+        })
+    }
+
+    /// Close the proxied connections, as if the server went away.
+    async fn drop_connections(&self) {
+        for connection in self.connections.lock().expect("Not poisoned").drain(..) {
+            connection.abort();
+        }
+        // Give the client a moment to notice.
+        tokio::time::sleep(Duration::from_millis(200)).await;
+    }
+}
@@ -156,3 +156,108 @@ async fn test_authenticate_success() -> anyhow::Result<()> {
 async fn test_authenticate_wrong_password() -> anyhow::Result<()> {
     dispatch_parallel_test(client_test_cases::test_authenticate_wrong_password).await
 }
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_compare() -> anyhow::Result<()> {
+    dispatch_parallel_test(client_test_cases::test_compare).await
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_move_entry() -> anyhow::Result<()> {
+    dispatch_parallel_test(client_test_cases::test_move_entry).await
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_rename() -> anyhow::Result<()> {
+    dispatch_parallel_test(client_test_cases::test_rename).await
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_delete_subtree() -> anyhow::Result<()> {
+    dispatch_parallel_test(client_test_cases::test_delete_subtree).await
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_change_and_reset_password() -> anyhow::Result<()> {
+    dispatch_parallel_test(client_test_cases::test_change_and_reset_password).await
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_who_am_i() -> anyhow::Result<()> {
+    dispatch_parallel_test(client_test_cases::test_who_am_i).await
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_authenticate_empty_password() -> anyhow::Result<()> {
+    dispatch_parallel_test(client_test_cases::test_authenticate_empty_password).await
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_authenticate_and_fetch() -> anyhow::Result<()> {
+    dispatch_parallel_test(client_test_cases::test_authenticate_and_fetch).await
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_authenticate_direct() -> anyhow::Result<()> {
+    dispatch_parallel_test(client_test_cases::test_authenticate_direct).await
+}
+
+/// Clients returned to the pool must be usable by the next one to get them.
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_reuse_returned_client() -> anyhow::Result<()> {
+    let ldap_config = ldap_config()?;
+    let pool_size = NonZeroUsize::new(1).context("Wasn't non-zero")?;
+    let pool = build_connection_pool(ldap_config, pool_size).await?;
+
+    for _ in 0..3 {
+        let mut client = pool.get().await?;
+        client.who_am_i().await?;
+    }
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_operations_with_timeout() -> anyhow::Result<()> {
+    dispatch_parallel_test(client_test_cases::test_operations_with_timeout).await
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_search_size_limit() -> anyhow::Result<()> {
+    dispatch_parallel_test(client_test_cases::test_search_size_limit).await
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_search_all() -> anyhow::Result<()> {
+    dispatch_parallel_test(client_test_cases::test_search_all).await
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_find_one_and_get() -> anyhow::Result<()> {
+    dispatch_parallel_test(client_test_cases::test_find_one_and_get).await
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_streaming_search_as() -> anyhow::Result<()> {
+    dispatch_parallel_test(client_test_cases::test_streaming_search_as).await
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_search_with_referral_policies() -> anyhow::Result<()> {
+    dispatch_parallel_test(client_test_cases::test_search_with_referral_policies).await
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_search_options() -> anyhow::Result<()> {
+    dispatch_parallel_test(client_test_cases::test_search_options).await
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_browse() -> anyhow::Result<()> {
+    dispatch_parallel_test(client_test_cases::test_browse).await
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_search_page() -> anyhow::Result<()> {
+    dispatch_parallel_test(client_test_cases::test_search_page).await
+}